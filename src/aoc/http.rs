@@ -0,0 +1,446 @@
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+// How long to wait on the server before giving up.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A base URL like `https://host:port/prefix`, which paths get added onto.
+/// * `tls` - Whether this is an `https` URL.
+/// * `host` - Name or address of the server.
+/// * `port` - Port of the server, 80 or 443 if the URL doesn't say.
+/// * `prefix` - Path every request starts with, empty or starting with `/`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Url {
+    tls: bool,
+    host: String,
+    port: u16,
+    prefix: String,
+}
+
+impl Url {
+    /// Parses an `http` or `https` URL.
+    pub fn parse(url: &str) -> Result<Self, String> {
+        let (tls, rest) = if let Some(rest) = url.strip_prefix("https://") {
+            (true, rest)
+        } else if let Some(rest) = url.strip_prefix("http://") {
+            (false, rest)
+        } else {
+            return Err(format!("'{url}' is not an 'http://' or 'https://' URL!"));
+        };
+
+        let (authority, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse()
+                    .map_err(|err| format!("Invalid port in '{url}': {err}"))?,
+            ),
+            None => (authority, if tls { 443 } else { 80 }),
+        };
+
+        if host.is_empty() {
+            return Err(format!("'{url}' has no host!"));
+        }
+
+        let prefix = prefix.trim_end_matches('/');
+
+        Ok(Self {
+            tls,
+            host: host.to_string(),
+            port,
+            prefix: if prefix.is_empty() {
+                String::new()
+            } else {
+                format!("/{prefix}")
+            },
+        })
+    }
+
+    /// The full URL of `path` under this one.
+    pub fn join(&self, path: &str) -> String {
+        let scheme = if self.tls { "https" } else { "http" };
+        format!(
+            "{scheme}://{}:{}{}{path}",
+            self.host, self.port, self.prefix
+        )
+    }
+}
+
+/// What the server answered.
+/// * `status` - The status code, like 200.
+/// * `body` - The body of the response, decoded if it was sent in chunks.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Sends a `GET` request for `path` under `base`.
+pub fn get(base: &Url, path: &str, headers: &[(&str, &str)]) -> Result<Response, String> {
+    send(base, "GET", path, headers, None)
+}
+
+/// Sends a `POST` request for `path` under `base`, with `form` as an url-encoded body.
+pub fn post_form(
+    base: &Url,
+    path: &str,
+    headers: &[(&str, &str)],
+    form: &[(&str, &str)],
+) -> Result<Response, String> {
+    let body = form
+        .iter()
+        .map(|(key, value)| format!("{}={}", encode(key), encode(value)))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    send(base, "POST", path, headers, Some(&body))
+}
+
+/// Writes the request out over a fresh connection, and reads the whole response back.
+/// There is no TLS here, so `https` requests are handed to `curl` instead.
+fn send(
+    base: &Url,
+    method: &str,
+    path: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> Result<Response, String> {
+    let url = base.join(path);
+
+    if base.tls {
+        return send_with_curl(&url, method, headers, body);
+    }
+
+    let failed = |err: std::io::Error| format!("Request to {url} failed: {err}");
+
+    let mut stream = TcpStream::connect((base.host.as_str(), base.port)).map_err(failed)?;
+    stream.set_read_timeout(Some(TIMEOUT)).map_err(failed)?;
+    stream.set_write_timeout(Some(TIMEOUT)).map_err(failed)?;
+
+    let mut lines = vec![
+        format!("{method} {}{path} HTTP/1.1", base.prefix),
+        format!("Host: {}:{}", base.host, base.port),
+        format!("User-Agent: {}", env!("CARGO_PKG_NAME")),
+        "Connection: close".to_string(),
+    ];
+
+    lines.extend(
+        headers
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}")),
+    );
+
+    if let Some(body) = body {
+        lines.push("Content-Type: application/x-www-form-urlencoded".to_string());
+        lines.push(format!("Content-Length: {}", body.len()));
+    }
+
+    // A blank line ends the head, and whatever body there is follows it.
+    lines.push(String::new());
+    lines.push(body.unwrap_or_default().to_string());
+
+    stream
+        .write_all(lines.join("\r\n").as_bytes())
+        .map_err(failed)?;
+
+    // The connection is closed after the response, so everything up to then belongs to it.
+    let mut raw = vec![];
+    stream.read_to_end(&mut raw).map_err(failed)?;
+
+    parse_response(&raw).map_err(|err| format!("Bad response from {url}: {err}"))
+}
+
+/// Sends the request through `curl`, which decodes the body itself and is told to
+/// write the status code after it.
+fn send_with_curl(
+    url: &str,
+    method: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> Result<Response, String> {
+    let failed = |err: std::io::Error| format!("Request to {url} through curl failed: {err}");
+
+    let mut command = Command::new("curl");
+    command
+        .args(["--silent", "--show-error", "--request", method])
+        .args(["--max-time", &TIMEOUT.as_secs().to_string()])
+        .args(["--user-agent", env!("CARGO_PKG_NAME")])
+        // The headers are read from stdin, so the session token isn't in the process list.
+        .args(["--header", "@-", "--write-out", "\n%{http_code}"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    if let Some(body) = body {
+        command
+            .args([
+                "--header",
+                "Content-Type: application/x-www-form-urlencoded",
+            ])
+            .args(["--data-raw", body]);
+    }
+
+    let mut child = command.arg(url).spawn().map_err(failed)?;
+
+    let headers = headers
+        .iter()
+        .map(|(name, value)| format!("{name}: {value}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(headers.as_bytes()).map_err(failed)?;
+    }
+
+    let output = child.wait_with_output().map_err(failed)?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Request to {url} through curl failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout
+        .rsplit_once('\n')
+        .ok_or_else(|| format!("Bad response from {url}: curl gave no status"))?;
+
+    Ok(Response {
+        status: status
+            .parse()
+            .map_err(|err| format!("Bad response from {url}: invalid status '{status}': {err}"))?,
+        body: body.to_string(),
+    })
+}
+
+/// Splits a raw response into its status and body.
+fn parse_response(raw: &[u8]) -> Result<Response, String> {
+    let header_end = raw
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or("The headers never end")?;
+
+    let head = String::from_utf8_lossy(&raw[..header_end]);
+    let mut body = raw[header_end + 4..].to_vec();
+    let mut lines = head.lines();
+
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|status| status.parse().ok())
+        .ok_or("Missing status line")?;
+
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        match (name.trim().to_ascii_lowercase().as_str(), value.trim()) {
+            ("transfer-encoding", "chunked") => body = decode_chunks(&body)?,
+            ("content-length", length) => {
+                let length = length
+                    .parse()
+                    .map_err(|err| format!("Invalid content length: {err}"))?;
+                body.truncate(length);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Response {
+        status,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+/// Joins a body sent as chunks, each being a hexadecimal length line followed by the data.
+fn decode_chunks(mut raw: &[u8]) -> Result<Vec<u8>, String> {
+    let mut body = vec![];
+
+    loop {
+        let line_end = raw
+            .windows(2)
+            .position(|window| window == b"\r\n")
+            .ok_or("A chunk has no length")?;
+
+        let line = String::from_utf8_lossy(&raw[..line_end]);
+        let size = line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size, 16)
+            .map_err(|err| format!("Invalid chunk length '{size}': {err}"))?;
+
+        if size == 0 {
+            return Ok(body);
+        }
+
+        let data = raw
+            .get(line_end + 2..line_end + 2 + size)
+            .ok_or("A chunk is cut short")?;
+        body.extend_from_slice(data);
+
+        raw = raw.get(line_end + 4 + size..).unwrap_or_default();
+    }
+}
+
+/// Percent-encodes everything but the characters allowed as they are in forms.
+fn encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                char::from(byte).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// A stand-in server on localhost for tests.
+#[cfg(test)]
+pub mod mock {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Answers one connection with each of the `(status, body)` responses in turn.
+    /// Gives back the base URL to use, and a handle to the requests it received.
+    pub fn serve(responses: Vec<(u16, String)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let request = read_request(&mut BufReader::new(&stream));
+
+                    write!(
+                        stream,
+                        "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+
+                    request
+                })
+                .collect()
+        });
+
+        (url, handle)
+    }
+
+    /// Reads the head of a request, along with the body its content length says follows.
+    fn read_request(reader: &mut impl BufRead) -> String {
+        let mut request = String::new();
+        let mut length = 0;
+
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+
+            if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                length = value.trim().parse().unwrap();
+            }
+
+            request += &line;
+
+            if line == "\r\n" || line.is_empty() {
+                break;
+            }
+        }
+
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        request + &String::from_utf8(body).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls() {
+        let url = Url::parse("http://localhost:8080/aoc/").unwrap();
+        assert_eq!(
+            "http://localhost:8080/aoc/2022/day/1",
+            url.join("/2022/day/1")
+        );
+        assert_eq!(
+            "http://example.com:80/x",
+            Url::parse("http://example.com").unwrap().join("/x")
+        );
+        assert_eq!(
+            "https://adventofcode.com:443/2022/day/1/input",
+            Url::parse("https://adventofcode.com")
+                .unwrap()
+                .join("/2022/day/1/input")
+        );
+
+        assert!(Url::parse("ftp://example.com").is_err());
+        assert!(Url::parse("http://:80").is_err());
+    }
+
+    #[test]
+    fn responses() {
+        let chunked = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n";
+        assert_eq!(
+            Ok(Response {
+                status: 200,
+                body: "hello world".to_string()
+            }),
+            parse_response(chunked)
+        );
+
+        let sized = b"HTTP/1.1 404 Not Found\r\nContent-Length: 3\r\n\r\nnot";
+        assert_eq!(404, parse_response(sized).unwrap().status);
+        assert!(parse_response(b"HTTP/1.1 200 OK\r\n").is_err());
+
+        assert_eq!("a%20b%3D1%26c", encode("a b=1&c"));
+    }
+
+    #[test]
+    fn requests() {
+        let (url, server) = mock::serve(vec![(200, "fine".to_string())]);
+        let base = Url::parse(&url).unwrap();
+
+        let response = get(&base, "/2022/day/1/input", &[("Cookie", "a=b")]);
+        assert_eq!(
+            Ok(Response {
+                status: 200,
+                body: "fine".to_string()
+            }),
+            response
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: a=b\r\n"));
+    }
+
+    #[test]
+    #[ignore = "needs curl installed"]
+    fn curl_requests() {
+        let (url, server) = mock::serve(vec![(201, "made".to_string())]);
+        let url = Url::parse(&url).unwrap().join("/2022/day/1/answer");
+
+        assert_eq!(
+            Ok(Response {
+                status: 201,
+                body: "made".to_string()
+            }),
+            send_with_curl(&url, "POST", &[("Cookie", "a=b")], Some("level=1"))
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2022/day/1/answer HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: a=b\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=1"));
+    }
+}
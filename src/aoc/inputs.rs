@@ -0,0 +1,110 @@
+use super::{http, Config};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Where the input of a puzzle is cached under `cache_dir`.
+pub fn cache_path(cache_dir: &Path, year: u32, day: u32) -> PathBuf {
+    cache_dir
+        .join(year.to_string())
+        .join(format!("day{day}.txt"))
+}
+
+/// Gets the input of a puzzle, from the cache if it was fetched before,
+/// so it is only ever downloaded once and works offline afterwards.
+pub fn load(config: &Config, year: u32, day: u32) -> Result<String, String> {
+    let path = cache_path(&config.cache_dir, year, day);
+
+    if let Ok(input) = fs::read_to_string(&path) {
+        return Ok(input);
+    }
+
+    let input = download(config, year, day)?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("Could not create {}: {err}", dir.display()))?;
+    }
+
+    fs::write(&path, &input).map_err(|err| format!("Could not write {}: {err}", path.display()))?;
+    Ok(input)
+}
+
+/// Downloads the input of a puzzle with the session token.
+fn download(config: &Config, year: u32, day: u32) -> Result<String, String> {
+    let session = config
+        .session
+        .as_ref()
+        .ok_or("No session token, set AOC_SESSION or put it in the 'session' file of the cache")?;
+
+    let base = &config.base_url;
+    let path = format!("/{year}/day/{day}/input");
+    let response = http::get(base, &path, &[("Cookie", &format!("session={session}"))])?;
+
+    match response.status {
+        200 => Ok(response.body),
+        404 => Err(format!(
+            "The input of {year} day {day} isn't available (yet)!"
+        )),
+        status => Err(format!(
+            "Fetching {} failed with status {status}: {}",
+            base.join(&path),
+            response.body.lines().next().unwrap_or_default()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::{http::mock, testing::config};
+
+    #[test]
+    fn fetching_and_caching() {
+        let (url, server) = mock::serve(vec![(200, "1000\n2000\n".to_string())]);
+        let config = config(&url, "inputs-caching");
+
+        assert_eq!(Ok("1000\n2000\n".to_string()), load(&config, 2022, 1));
+
+        // The mock server is gone after one request, so this can only come from the cache.
+        let requests = server.join().unwrap();
+        assert_eq!(Ok("1000\n2000\n".to_string()), load(&config, 2022, 1));
+        assert!(cache_path(&config.cache_dir, 2022, 1).ends_with("2022/day1.txt"));
+
+        assert!(requests[0].starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=abc123\r\n"));
+
+        fs::remove_dir_all(config.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn failures() {
+        let (url, server) = mock::serve(vec![
+            (404, "Not found".to_string()),
+            (400, "Puzzle inputs differ by user.\n".to_string()),
+        ]);
+        let mut config = config(&url, "inputs-failures");
+
+        assert!(load(&config, 2030, 1)
+            .unwrap_err()
+            .contains("isn't available"));
+        assert!(load(&config, 2022, 2)
+            .unwrap_err()
+            .contains("status 400: Puzzle inputs differ"));
+        server.join().unwrap();
+
+        // Nothing failed gets cached, and nothing is sent without a session.
+        assert!(!cache_path(&config.cache_dir, 2022, 2).exists());
+        config.session = None;
+        assert!(load(&config, 2022, 2)
+            .unwrap_err()
+            .contains("No session token"));
+
+        // Nothing is listening on the port the mock server had any more.
+        config.session = Some("abc123".to_string());
+        assert!(load(&config, 2022, 2).unwrap_err().contains("failed"));
+
+        fs::remove_dir_all(config.cache_dir).unwrap();
+    }
+}
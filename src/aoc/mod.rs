@@ -0,0 +1,179 @@
+mod http;
+mod inputs;
+mod submit;
+
+use crate::{parse, solutions};
+use std::{
+    env, fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+// Where puzzles are fetched from when nothing else is configured.
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings for talking to the puzzle site, read from the environment.
+/// * `base_url` - Site to talk to, from `AOC_BASE_URL`, like a local stand-in server.
+/// * `session` - Session token of the logged in user, from `AOC_SESSION` or
+///   a `session` file in the cache directory.
+/// * `cache_dir` - Where fetched inputs are kept, from `AOC_CACHE_DIR`, or
+///   `advent-of-code` in the user's cache directory.
+pub struct Config {
+    pub base_url: http::Url,
+    pub session: Option<String>,
+    pub cache_dir: PathBuf,
+}
+
+impl Config {
+    /// Reads the settings from the environment, failing if there is nowhere to cache inputs
+    /// or the site given isn't a valid URL.
+    pub fn from_env() -> Result<Self, String> {
+        let cache_dir = cache_dir()?;
+
+        let base_url = http::Url::parse(
+            &env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string()),
+        )
+        .map_err(|err| format!("Invalid AOC_BASE_URL: {err}"))?;
+
+        let session = env::var("AOC_SESSION")
+            .ok()
+            .or_else(|| fs::read_to_string(cache_dir.join("session")).ok())
+            .map(|session| session.trim().to_string())
+            .filter(|session| !session.is_empty());
+
+        Ok(Self {
+            base_url,
+            session,
+            cache_dir,
+        })
+    }
+}
+
+/// Where fetched inputs are kept, see [`Config`].
+fn cache_dir() -> Result<PathBuf, String> {
+    env::var_os("AOC_CACHE_DIR")
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var_os("XDG_CACHE_HOME").map(|dir| PathBuf::from(dir).join("advent-of-code"))
+        })
+        .or_else(|| env::var_os("HOME").map(|dir| PathBuf::from(dir).join(".cache/advent-of-code")))
+        .ok_or_else(|| "No cache directory, set AOC_CACHE_DIR".to_string())
+}
+
+/// Gets the real input of a puzzle, fetching it into the cache first if it isn't there yet.
+/// Panics if it can't be had, since there is nothing to solve without it.
+/// Days made with `template new` read their input through this, instead of committing it.
+#[allow(dead_code)]
+pub fn puzzle_input(year: u32, day: u32) -> String {
+    Config::from_env()
+        .and_then(|config| inputs::load(&config, year, day))
+        .unwrap_or_else(|err| panic!("Could not get the input of {year} day {day}: {err}"))
+}
+
+/// Fetches the input of a puzzle into the cache from `template fetch <year> <day>`,
+/// reusing the cached input if it is already there.
+pub fn fetch(args: &[String]) {
+    let result = puzzle(args).and_then(|(year, day)| {
+        let config = Config::from_env()?;
+        let input = inputs::load(&config, year, day)?;
+        Ok((inputs::cache_path(&config.cache_dir, year, day), input))
+    });
+
+    match result {
+        Ok((path, input)) => println!(
+            "Input cached at {} ({} lines)",
+            path.display(),
+            input.lines().count()
+        ),
+        Err(err) => println!("Could not fetch the input: {err}"),
+    }
+}
+
+/// Works out the answer to a part from `template submit <year> <day> <part>` by running
+/// its solution on the real input, and submits it unless earlier attempts show it
+/// would be wasted.
+pub fn submit(args: &[String]) {
+    let result = puzzle(args).and_then(|(year, day)| {
+        let part = args.get(4).ok_or("Expected a part after the day")?;
+        let puzzle = submit::Puzzle {
+            year,
+            day,
+            part: parse::prefixed_integer(part, "part")
+                .map_err(|err| format!("Invalid part '{part}': {err}"))?,
+        };
+
+        let solve = solutions::solver(year, day)
+            .ok_or_else(|| format!("There is no solution to {year} day {day} yet"))?;
+
+        let config = Config::from_env()?;
+        let [first, second] = solve(&inputs::load(&config, year, day)?);
+
+        let answer = match puzzle.part {
+            1 => first,
+            2 => second,
+            part => return Err(format!("Part {part} is not 1 or 2!")),
+        }
+        .ok_or_else(|| format!("Part {} of {year} day {day} isn't solved yet", puzzle.part))?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|err| format!("The clock is before 1970: {err}"))?
+            .as_secs();
+
+        println!("Submitting '{answer}'");
+        submit::submit(&config, puzzle, &answer, now)
+    });
+
+    match result {
+        Ok(submit::Verdict::Wait(seconds)) => {
+            println!("Answered too recently, try again in {seconds}s.");
+        }
+        Ok(verdict) => println!("Verdict: {verdict}"),
+        Err(err) => println!("Could not submit the answer: {err}"),
+    }
+}
+
+/// Reads the year and day of a puzzle from the arguments after the subcommand.
+fn puzzle(args: &[String]) -> Result<(u32, u32), String> {
+    let (Some(year), Some(day)) = (args.get(2), args.get(3)) else {
+        return Err(format!(
+            "Expected a year and a day, like '{} 2022 5'",
+            args[1]
+        ));
+    };
+
+    let number = |arg: &str, prefix| {
+        parse::prefixed_integer(arg, prefix)
+            .map_err(|err| format!("Invalid {prefix} '{arg}': {err}"))
+    };
+
+    Ok((number(year, "year")?, number(day, "day")?))
+}
+
+/// Things shared by the tests that need a place on disk or a mock site to work with.
+#[cfg(test)]
+pub mod testing {
+    use super::{http, Config};
+    use std::{env, fs, path::PathBuf, process};
+
+    /// Gives a fresh, empty directory for the test called `name` to work in.
+    pub fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("template-{name}-{}", process::id()));
+
+        if dir.exists() {
+            fs::remove_dir_all(&dir).unwrap();
+        }
+
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Settings for talking to the mock site at `base_url`, caching in a fresh directory.
+    pub fn config(base_url: &str, name: &str) -> Config {
+        Config {
+            base_url: http::Url::parse(base_url).unwrap(),
+            session: Some("abc123".to_string()),
+            cache_dir: temp_dir(name),
+        }
+    }
+}
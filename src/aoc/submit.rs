@@ -0,0 +1,397 @@
+use super::{http, Config};
+use std::{
+    fmt::{self, Display},
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    str::FromStr,
+};
+
+/// What the site made of a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about which way.
+    Wrong,
+    /// Answered too recently, with the seconds left to wait before trying again.
+    Wait(u64),
+    /// The part was solved already, or isn't unlocked yet.
+    WrongLevel,
+    /// Anything else the site said.
+    Unknown,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the page sent back after submitting.
+    pub fn from_page(page: &str) -> Self {
+        let page = page.to_ascii_lowercase();
+
+        if page.contains("that's the right answer") {
+            Self::Correct
+        } else if page.contains("answer too recently") {
+            Self::Wait(wait_seconds(&page).unwrap_or(60))
+        } else if page.contains("your answer is too high") {
+            Self::TooHigh
+        } else if page.contains("your answer is too low") {
+            Self::TooLow
+        } else if page.contains("not the right answer") {
+            Self::Wrong
+        } else if page.contains("solving the right level") {
+            Self::WrongLevel
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+/// Reads how long to wait from text like `you have 1m 5s left to wait`.
+fn wait_seconds(page: &str) -> Option<u64> {
+    let start = page.find("you have ")? + "you have ".len();
+    let end = start + page[start..].find(" left to wait")?;
+
+    page[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.len().checked_sub(1)?);
+            let number = number.parse::<u64>().ok()?;
+
+            match unit {
+                "h" => Some(number * 3600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+/// Verdicts are written to the attempt log like `too-high` or `wait 60`.
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "too-high"),
+            Self::TooLow => write!(f, "too-low"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::Wait(seconds) => write!(f, "wait {seconds}"),
+            Self::WrongLevel => write!(f, "wrong-level"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Ok(match text {
+            "correct" => Self::Correct,
+            "too-high" => Self::TooHigh,
+            "too-low" => Self::TooLow,
+            "wrong" => Self::Wrong,
+            "wrong-level" => Self::WrongLevel,
+            "unknown" => Self::Unknown,
+            _ => Self::Wait(
+                text.strip_prefix("wait ")
+                    .and_then(|seconds| seconds.parse().ok())
+                    .ok_or_else(|| format!("Unknown verdict '{text}'!"))?,
+            ),
+        })
+    }
+}
+
+/// A part of a puzzle to submit to.
+/// * `year` - Year of the event.
+/// * `day` - Day of the puzzle, from 1 to 25.
+/// * `part` - Part of the puzzle, 1 or 2.
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+}
+
+/// An answer that was submitted before.
+/// * `time` - When it was submitted, in seconds since the unix epoch.
+/// * `answer` - The answer itself.
+/// * `verdict` - What the site made of it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Attempt {
+    pub time: u64,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Where every attempt at a part is recorded, one tab separated line each.
+pub fn log_path(config: &Config, puzzle: Puzzle) -> PathBuf {
+    config.cache_dir.join(puzzle.year.to_string()).join(format!(
+        "day{}-part{}-attempts.tsv",
+        puzzle.day, puzzle.part
+    ))
+}
+
+/// Reads every recorded attempt at a part, which there are none of if it has no log yet.
+pub fn attempts(config: &Config, puzzle: Puzzle) -> Result<Vec<Attempt>, String> {
+    let path = log_path(config, puzzle);
+
+    let Ok(log) = fs::read_to_string(&path) else {
+        return Ok(vec![]);
+    };
+
+    log.lines()
+        .map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let (Some(time), Some(answer), Some(verdict)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(format!("Malformed attempt '{line}' in {}!", path.display()));
+            };
+
+            Ok(Attempt {
+                time: time
+                    .parse()
+                    .map_err(|err| format!("Invalid time in '{line}': {err}"))?,
+                answer: answer.to_string(),
+                verdict: verdict.parse()?,
+            })
+        })
+        .collect()
+}
+
+/// Checks the earlier attempts to make sure submitting `answer` at `now` can't be wasted:
+/// the part isn't solved yet, the site isn't asking to wait, the answer wasn't tried
+/// before, and it isn't ruled out by an earlier too high or too low answer.
+pub fn check(attempts: &[Attempt], answer: &str, now: u64) -> Result<(), String> {
+    if let Some(solved) = attempts
+        .iter()
+        .find(|attempt| attempt.verdict == Verdict::Correct)
+    {
+        return Err(format!("Already solved, with '{}'!", solved.answer));
+    }
+
+    if let Some(&Attempt {
+        time,
+        verdict: Verdict::Wait(seconds),
+        ..
+    }) = attempts.last()
+    {
+        if now < time + seconds {
+            return Err(format!(
+                "Still rate limited, wait {}s more!",
+                time + seconds - now
+            ));
+        }
+    }
+
+    let number = answer.parse::<i128>().ok();
+
+    for attempt in attempts {
+        let previous = attempt.answer.parse::<i128>().ok();
+
+        let ruled_out = match (&attempt.verdict, number, previous) {
+            // These don't say anything about the answer, so it can be tried again.
+            (Verdict::Wait(_) | Verdict::WrongLevel | Verdict::Unknown, ..) => false,
+            (Verdict::TooHigh, Some(number), Some(previous)) => number >= previous,
+            (Verdict::TooLow, Some(number), Some(previous)) => number <= previous,
+            _ => attempt.answer == answer,
+        };
+
+        if ruled_out {
+            return Err(format!(
+                "'{answer}' is ruled out, '{}' was {}!",
+                attempt.answer, attempt.verdict
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Submits `answer` to a part at `now`, unless the earlier attempts show it
+/// would be wasted, and records the attempt along with the verdict.
+pub fn submit(config: &Config, puzzle: Puzzle, answer: &str, now: u64) -> Result<Verdict, String> {
+    let answer = answer.trim();
+
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Err(format!("'{answer}' is not a single answer!"));
+    }
+
+    if !(1..=2).contains(&puzzle.part) {
+        return Err(format!("Part {} is not 1 or 2!", puzzle.part));
+    }
+
+    check(&attempts(config, puzzle)?, answer, now)?;
+
+    let session = config
+        .session
+        .as_ref()
+        .ok_or("No session token, set AOC_SESSION or put it in the 'session' file of the cache")?;
+
+    let base = &config.base_url;
+    let path = format!("/{}/day/{}/answer", puzzle.year, puzzle.day);
+    let response = http::post_form(
+        base,
+        &path,
+        &[("Cookie", &format!("session={session}"))],
+        &[("level", &puzzle.part.to_string()), ("answer", answer)],
+    )?;
+
+    if response.status != 200 {
+        return Err(format!(
+            "Submitting to {} failed with status {}!",
+            base.join(&path),
+            response.status
+        ));
+    }
+
+    let verdict = Verdict::from_page(&response.body);
+    record(config, puzzle, now, answer, &verdict)?;
+    Ok(verdict)
+}
+
+/// Adds an attempt to the end of the log of its part.
+fn record(
+    config: &Config,
+    puzzle: Puzzle,
+    now: u64,
+    answer: &str,
+    verdict: &Verdict,
+) -> Result<(), String> {
+    let path = log_path(config, puzzle);
+    let failed = |err| format!("Could not record the attempt in {}: {err}", path.display());
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(failed)?;
+    }
+
+    let mut log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(failed)?;
+
+    writeln!(log, "{now}\t{answer}\t{verdict}").map_err(failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::{http::mock, testing::config};
+
+    const PUZZLE: Puzzle = Puzzle {
+        year: 2022,
+        day: 1,
+        part: 1,
+    };
+
+    fn page(text: &str) -> String {
+        format!("<html><body><main><article><p>{text}</p></article></main></body></html>")
+    }
+
+    fn attempt(time: u64, answer: &str, verdict: Verdict) -> Attempt {
+        Attempt {
+            time,
+            answer: answer.to_string(),
+            verdict,
+        }
+    }
+
+    #[test]
+    fn verdicts() {
+        assert_eq!(
+            Verdict::Correct,
+            Verdict::from_page(&page(
+                "That's the right answer! You are one gold star closer."
+            ))
+        );
+        assert_eq!(
+            Verdict::TooHigh,
+            Verdict::from_page(&page(
+                "That's not the right answer; your answer is too high."
+            ))
+        );
+        assert_eq!(
+            Verdict::Wrong,
+            Verdict::from_page(&page("That's not the right answer. If you're stuck, ..."))
+        );
+        assert_eq!(
+            Verdict::Wait(272),
+            Verdict::from_page(&page(
+                "You gave an answer too recently. You have 4m 32s left to wait."
+            ))
+        );
+        assert_eq!(
+            Verdict::WrongLevel,
+            Verdict::from_page(&page("You don't seem to be solving the right level."))
+        );
+        assert_eq!(Verdict::Unknown, Verdict::from_page("<html></html>"));
+
+        for verdict in [Verdict::TooLow, Verdict::Wait(30), Verdict::WrongLevel] {
+            assert_eq!(Ok(verdict.clone()), verdict.to_string().parse());
+        }
+    }
+
+    #[test]
+    fn guards() {
+        let attempts = [
+            attempt(100, "500", Verdict::TooHigh),
+            attempt(200, "300", Verdict::TooLow),
+            attempt(300, "abc", Verdict::Wrong),
+            attempt(400, "400", Verdict::Wait(60)),
+        ];
+
+        assert!(check(&attempts, "400", 459)
+            .unwrap_err()
+            .contains("wait 1s more"));
+        assert_eq!(Ok(()), check(&attempts, "400", 460));
+        assert!(check(&attempts, "501", 460).is_err());
+        assert!(check(&attempts, "250", 460).is_err());
+        assert!(check(&attempts, "abc", 460).is_err());
+
+        let solved = [attempt(100, "42", Verdict::Correct)];
+        assert!(check(&solved, "43", 200)
+            .unwrap_err()
+            .contains("Already solved"));
+    }
+
+    #[test]
+    fn submitting() {
+        let (url, server) = mock::serve(vec![
+            (
+                200,
+                page("That's not the right answer; your answer is too low."),
+            ),
+            (200, page("That's the right answer!")),
+        ]);
+        let config = config(&url, "submit-submitting");
+
+        assert_eq!(Ok(Verdict::TooLow), submit(&config, PUZZLE, "41", 1_000));
+        assert_eq!(
+            Ok(Verdict::Correct),
+            submit(&config, PUZZLE, " 42\n", 1_100)
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2022/day/1/answer HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=abc123\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=1&answer=41"));
+
+        // Every attempt is recorded, and the guards stop anything else from being sent.
+        assert_eq!(
+            Ok(vec![
+                attempt(1_000, "41", Verdict::TooLow),
+                attempt(1_100, "42", Verdict::Correct)
+            ]),
+            attempts(&config, PUZZLE)
+        );
+        assert!(submit(&config, PUZZLE, "43", 1_200)
+            .unwrap_err()
+            .contains("Already solved"));
+        assert!(submit(&config, Puzzle { part: 3, ..PUZZLE }, "1", 1_200).is_err());
+        assert!(submit(&config, PUZZLE, "1 2", 1_200).is_err());
+
+        fs::remove_dir_all(config.cache_dir).unwrap();
+    }
+}
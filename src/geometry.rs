@@ -0,0 +1,214 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+/// A point on the integer plane, with `y` growing downwards like the rows of an input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    /// Creates a new `Point`.
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+}
+
+/// A straight line between two points, both ends included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Segment {
+    /// Creates a new `Segment` from `start` to `end`.
+    pub const fn new(start: Point, end: Point) -> Self {
+        Self { start, end }
+    }
+
+    /// Checks if the segment runs along a row or a column.
+    pub const fn is_axis_aligned(&self) -> bool {
+        self.start.x == self.end.x || self.start.y == self.end.y
+    }
+
+    /// Checks if the segment runs at exactly 45 degrees.
+    pub const fn is_diagonal(&self) -> bool {
+        (self.end.x - self.start.x).abs() == (self.end.y - self.start.y).abs()
+            && self.start.x != self.end.x
+    }
+
+    /// Iterates through every point the segment passes over, from `start` to `end`,
+    /// using Bresenham's algorithm so lines at any angle get a point per step.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (dx, dy) = (
+            (self.end.x - self.start.x).abs(),
+            -(self.end.y - self.start.y).abs(),
+        );
+        let (step_x, step_y) = (
+            (self.end.x - self.start.x).signum(),
+            (self.end.y - self.start.y).signum(),
+        );
+
+        let end = self.end;
+        let mut error = dx + dy;
+
+        std::iter::successors(Some(self.start), move |&point| {
+            if point == end {
+                return None;
+            }
+
+            let mut next = point;
+            let doubled = 2 * error;
+
+            if doubled >= dy {
+                error += dy;
+                next.x += step_x;
+            }
+
+            if doubled <= dx {
+                error += dx;
+                next.y += step_y;
+            }
+
+            Some(next)
+        })
+    }
+}
+
+/// Counts how often each point was covered, only storing the points that were.
+#[derive(Clone, Debug, Default)]
+pub struct PointCounter {
+    counts: HashMap<Point, usize>,
+}
+
+impl PointCounter {
+    /// Creates a new, empty `PointCounter`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Covers a single point once more.
+    pub fn add(&mut self, point: Point) {
+        *self.counts.entry(point).or_default() += 1;
+    }
+
+    /// Covers every point of the segment once more.
+    pub fn add_segment(&mut self, segment: &Segment) {
+        for point in segment.points() {
+            self.add(point);
+        }
+    }
+
+    /// How often `point` was covered.
+    pub fn count(&self, point: Point) -> usize {
+        self.counts.get(&point).copied().unwrap_or_default()
+    }
+
+    /// Amount of points covered at least `min` times.
+    pub fn at_least(&self, min: usize) -> usize {
+        self.counts.values().filter(|&&count| count >= min).count()
+    }
+
+    /// The top left and bottom right corners of the box around every covered point.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.counts.keys().fold(None, |bounds, &point| {
+            let (min, max) = bounds.unwrap_or((point, point));
+
+            Some((
+                Point::new(min.x.min(point.x), min.y.min(point.y)),
+                Point::new(max.x.max(point.x), max.y.max(point.y)),
+            ))
+        })
+    }
+}
+
+/// Draws the box around every covered point, with `.` for points that weren't
+/// covered and the count for the rest (or `+` past 9).
+impl Display for PointCounter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.bounds() else {
+            return Ok(());
+        };
+
+        for y in min.y..=max.y {
+            if y > min.y {
+                writeln!(f)?;
+            }
+
+            for x in min.x..=max.x {
+                match self.count(Point::new(x, y)) {
+                    0 => write!(f, ".")?,
+                    count @ 1..=9 => write!(f, "{count}")?,
+                    _ => write!(f, "+")?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn segment((x1, y1): (i64, i64), (x2, y2): (i64, i64)) -> Segment {
+        Segment::new(Point::new(x1, y1), Point::new(x2, y2))
+    }
+
+    fn points(segment: &Segment) -> Vec<(i64, i64)> {
+        segment.points().map(|point| (point.x, point.y)).collect()
+    }
+
+    #[test]
+    fn rasterising() {
+        assert_eq!(
+            vec![(1, 1), (1, 2), (1, 3)],
+            points(&segment((1, 1), (1, 3)))
+        );
+        assert_eq!(
+            vec![(9, 7), (8, 7), (7, 7)],
+            points(&segment((9, 7), (7, 7)))
+        );
+        assert_eq!(
+            vec![(9, 7), (8, 8), (7, 9)],
+            points(&segment((9, 7), (7, 9)))
+        );
+        assert_eq!(vec![(4, 4)], points(&segment((4, 4), (4, 4))));
+
+        // Steeper lines still get one point for every row they cross.
+        assert_eq!(
+            vec![(0, 0), (1, 1), (1, 2), (2, 3)],
+            points(&segment((0, 0), (2, 3)))
+        );
+
+        assert!(segment((0, 5), (3, 5)).is_axis_aligned());
+        assert!(segment((0, 5), (3, 2)).is_diagonal());
+        assert!(!segment((0, 5), (3, 3)).is_diagonal());
+        assert!(!segment((1, 1), (1, 1)).is_diagonal());
+    }
+
+    #[test]
+    fn counting() {
+        let mut counter = PointCounter::new();
+        assert_eq!(None, counter.bounds());
+        assert_eq!("", counter.to_string());
+
+        counter.add_segment(&segment((0, 0), (2, 0)));
+        counter.add_segment(&segment((2, -1), (2, 1)));
+        counter.add_segment(&segment((0, 0), (1, 1)));
+
+        assert_eq!(2, counter.count(Point::new(2, 0)));
+        assert_eq!(0, counter.count(Point::new(5, 5)));
+        assert_eq!(2, counter.at_least(2));
+        assert_eq!(
+            Some((Point::new(0, -1), Point::new(2, 1))),
+            counter.bounds()
+        );
+        assert_eq!("..1\n212\n.11", counter.to_string());
+    }
+}
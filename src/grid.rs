@@ -0,0 +1,333 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use crate::parse::ParseError;
+
+/// Position of a cell as `(x, y)`, with `x` counting columns from the left
+/// and `y` counting rows from the top.
+pub type Position = (usize, usize);
+
+/// Offsets to the orthogonal neighbours of a cell.
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets to every neighbour of a cell, diagonals included.
+const ALL_AROUND: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid of cells, stored row after row in a single vector.
+/// * `width` - Amount of columns.
+/// * `height` - Amount of rows.
+/// * `cells` - Every cell, with the cell at `(x, y)` at index `y * width + x`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of `width` columns out of cells given row after row,
+    /// failing if they don't fill a whole amount of rows.
+    pub fn new(width: usize, cells: Vec<T>) -> Result<Self, String> {
+        if width == 0 || cells.len() % width != 0 {
+            return Err(format!(
+                "{} cells can't be split into rows of {width}!",
+                cells.len()
+            ));
+        }
+
+        Ok(Self {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    /// Creates a grid with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid from its rows, failing if they aren't all the same length.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Result<Self, String> {
+        let mut rows = rows.into_iter().peekable();
+        let width = rows.peek().map_or(0, Vec::len);
+        let mut cells = vec![];
+
+        for (y, row) in rows.enumerate() {
+            if row.len() != width {
+                return Err(format!(
+                    "Row {y} has {} cells, but the first one has {width}!",
+                    row.len()
+                ));
+            }
+
+            cells.extend(row);
+        }
+
+        Self::new(width, cells)
+    }
+
+    /// Parses a block of characters, one row per line, converting every character with `cell`.
+    pub fn parse(
+        text: &str,
+        mut cell: impl FnMut(char) -> Result<T, String>,
+    ) -> Result<Self, ParseError> {
+        let rows = text
+            .lines()
+            .map(|line| {
+                line.char_indices()
+                    .map(|(idx, char)| {
+                        cell(char).map_err(|err| ParseError::at(line, idx, err).within(text, line))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_rows(rows).map_err(ParseError::new)
+    }
+
+    /// Amount of columns in the grid.
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Amount of rows in the grid.
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Gets the cell at `(x, y)`, if it is within the grid.
+    pub fn get(&self, (x, y): Position) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    /// Gets the cell at `(x, y)` mutably, if it is within the grid.
+    pub fn get_mut(&mut self, (x, y): Position) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// Iterates through every cell, row after row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Iterates mutably through every cell, row after row.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Iterates through the position of every cell, row after row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Gets the cells of row `y`.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterates through the rows from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Iterates through the cells of column `x`, from top to bottom,
+    /// or through nothing if `x` is past the right edge.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        // Skipping past the right edge would otherwise start the column on a later row.
+        let height = if x < self.width { self.height } else { 0 };
+
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .take(height)
+    }
+
+    /// Iterates through the columns from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Iterates down and to the right from `start`, until the edge of the grid.
+    pub fn diagonal(&self, start: Position) -> impl Iterator<Item = &T> {
+        self.walk(start, (1, 1))
+    }
+
+    /// Iterates down and to the left from `start`, until the edge of the grid.
+    pub fn anti_diagonal(&self, start: Position) -> impl Iterator<Item = &T> {
+        self.walk(start, (-1, 1))
+    }
+
+    /// Iterates through the positions of the up to 4 cells sharing an edge with `position`.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ORTHOGONAL
+            .iter()
+            .filter_map(move |&offset| self.step(position, offset))
+    }
+
+    /// Iterates through the positions of the up to 8 cells touching `position`.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ALL_AROUND
+            .iter()
+            .filter_map(move |&offset| self.step(position, offset))
+    }
+
+    /// Creates a grid of the same size out of the converted cells.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Moves from `position` by `offset`, if that stays within the grid.
+    fn step(&self, (x, y): Position, (dx, dy): (isize, isize)) -> Option<Position> {
+        let next = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        (next.0 < self.width && next.1 < self.height).then_some(next)
+    }
+
+    /// Iterates from `start` in steps of `offset`, until the edge of the grid.
+    fn walk(&self, start: Position, offset: (isize, isize)) -> impl Iterator<Item = &T> {
+        let start = self.get(start).map(|_| start);
+
+        std::iter::successors(start, move |&position| self.step(position, offset))
+            .map(|position| &self[position])
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("Position {position:?} is outside of the grid."))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("Position {position:?} is outside of the grid."))
+    }
+}
+
+/// Renders every row on its own line, with the cells written one after another.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters() -> Grid<char> {
+        Grid::parse("abc\ndef\nghi\njkl", Ok).unwrap()
+    }
+
+    #[test]
+    fn building() {
+        let grid = letters();
+        assert_eq!((3, 4), (grid.width(), grid.height()));
+        assert_eq!('f', grid[(2, 1)]);
+        assert_eq!(None, grid.get((3, 0)));
+        assert_eq!("abc\ndef\nghi\njkl", grid.to_string());
+
+        assert_eq!(
+            Grid::new(2, vec![1, 2, 3, 4]),
+            Grid::from_rows(vec![vec![1, 2], vec![3, 4]])
+        );
+        assert!(Grid::new(2, vec![1, 2, 3]).is_err());
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+
+        let digits = Grid::parse("12\n3x", |char| {
+            char.to_digit(10)
+                .ok_or_else(|| format!("'{char}' is not a digit"))
+        });
+        assert_eq!(
+            "line 2, column 2: 'x' is not a digit",
+            digits.unwrap_err().to_string()
+        );
+
+        let mut grid = Grid::filled(2, 2, '.');
+        grid[(1, 0)] = '#';
+        assert_eq!(".#\n..", grid.to_string());
+        assert_eq!(
+            "01\n00",
+            grid.map(|&cell| u8::from(cell == '#')).to_string()
+        );
+    }
+
+    #[test]
+    fn lines_through_the_grid() {
+        let grid = letters();
+        let collect = |cells: &mut dyn Iterator<Item = &char>| cells.collect::<String>();
+
+        assert_eq!(['g', 'h', 'i'], grid.row(2));
+        assert_eq!("behk", collect(&mut grid.column(1)));
+        assert_eq!("", collect(&mut grid.column(3)));
+        assert_eq!("", collect(&mut grid.column(7)));
+        assert_eq!(
+            vec!["adgj", "behk", "cfil"],
+            grid.columns()
+                .map(|mut column| collect(&mut column))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("aei", collect(&mut grid.diagonal((0, 0))));
+        assert_eq!("dhl", collect(&mut grid.diagonal((0, 1))));
+        assert_eq!("ceg", collect(&mut grid.anti_diagonal((2, 0))));
+        assert_eq!("", collect(&mut grid.diagonal((5, 5))));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = letters();
+
+        assert_eq!(
+            vec![(1, 0), (0, 1)],
+            grid.neighbours4((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(1, 0), (0, 1), (2, 1), (1, 2)],
+            grid.neighbours4((1, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(8, grid.neighbours8((1, 1)).count());
+        assert_eq!(
+            vec![(1, 2), (2, 2), (1, 3)],
+            grid.neighbours8((2, 3)).collect::<Vec<_>>()
+        );
+        assert_eq!(12, grid.positions().count());
+    }
+}
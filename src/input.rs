@@ -0,0 +1,88 @@
+use std::{fs, path::Path};
+
+/// Byte order mark that some editors put at the start of a file.
+const BOM: char = '\u{feff}';
+
+/// Normalises puzzle input so that every day can rely on the same layout:
+/// * A leading byte order mark is removed.
+/// * `\r\n` and lone `\r` line endings become `\n`.
+/// * Trailing whitespace is trimmed from every line (leading whitespace is kept).
+/// * Blank lines at the end of the input are dropped.
+pub fn normalise(file_contents: &str) -> String {
+    let mut normalised = file_contents
+        .trim_start_matches(BOM)
+        .split("\r\n")
+        .flat_map(|segment| segment.split(['\r', '\n']))
+        .map(str::trim_end)
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    normalised.truncate(normalised.trim_end().len());
+    normalised
+}
+
+/// Normalises a single line read from a stream, see [`normalise`].
+pub fn normalise_line(line: &str) -> &str {
+    line.trim_start_matches(BOM).trim_end()
+}
+
+/// Splits normalised input into paragraphs, treating any run of blank lines as one separator.
+pub fn paragraphs(file_contents: &str) -> impl Iterator<Item = &str> {
+    file_contents
+        .split("\n\n")
+        .map(|paragraph| paragraph.trim_matches('\n'))
+        .filter(|paragraph| !paragraph.is_empty())
+}
+
+/// Reads the whole file at `path`, panicking with the path if it can't be read.
+pub fn read(path: impl AsRef<Path>) -> String {
+    let path = path.as_ref();
+
+    fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("Could not read file '{}': {err}", path.display()))
+}
+
+/// Reads the file at `path` if one was given, otherwise falls back to `default`.
+pub fn read_or(path: Option<impl AsRef<Path>>, default: impl FnOnce() -> String) -> String {
+    path.map_or_else(default, read)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalising() {
+        assert_eq!(
+            "1\n2\n\n3",
+            normalise("\u{feff}1\r\n2  \r\n\r\n3\r\n\r\n  \n")
+        );
+        assert_eq!("a\nb\n  c", normalise("a\rb\t\n  c"));
+        assert_eq!("", normalise("\n\n"));
+        assert_eq!("42", normalise_line("\u{feff}42 \r"));
+    }
+
+    #[test]
+    fn splitting_paragraphs() {
+        let normalised = normalise("1\n2\r\n\r\n\r\n\r\n3\n \n4\n\n");
+
+        assert_eq!(
+            vec!["1\n2", "3", "4"],
+            paragraphs(&normalised).collect::<Vec<_>>()
+        );
+        assert_eq!(0, paragraphs("").count());
+    }
+
+    #[test]
+    fn reading_files() {
+        assert_eq!(
+            "1000\n2000",
+            &read("src/year2022/day1/calories-ex.txt")[..9]
+        );
+        assert_eq!(
+            read("src/year2022/day1/calories-ex.txt"),
+            read_or(Some("src/year2022/day1/calories-ex.txt"), String::new)
+        );
+        assert_eq!("none", read_or(None::<&str>, || "none".to_string()));
+    }
+}
@@ -0,0 +1,297 @@
+/// Integer types an interval can step through one point at a time, without overflowing.
+pub trait Discrete: Copy + Ord {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_discrete {
+    ($($int:ty),*) => {
+        $(
+            impl Discrete for $int {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$int>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$int>::checked_sub(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_discrete!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Inclusive interval `[start, end]` over an ordered type, like the section ranges of elves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    /// Creates a new `Interval`, panics if `start` is past `end`.
+    pub fn new(start: T, end: T) -> Self {
+        assert!(start <= end, "Interval start must not be past its end.");
+
+        Self { start, end }
+    }
+
+    /// Checks if `point` lies within the interval.
+    pub fn contains_point(&self, point: T) -> bool {
+        self.start <= point && point <= self.end
+    }
+
+    /// Checks if `other` lies entirely within the interval.
+    pub fn contains(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Checks if the interval shares at least one point with `other`.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Gets the points shared by both intervals, if there are any.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.overlaps(other)
+            .then(|| Self::new(self.start.max(other.start), self.end.min(other.end)))
+    }
+}
+
+impl<T: Discrete> Interval<T> {
+    /// Amount of discrete points in the interval, both ends included,
+    /// or `None` if that amount doesn't fit in `T`.
+    pub fn length(&self) -> Option<T> {
+        self.end.checked_sub(self.start)?.checked_add(T::ONE)
+    }
+
+    /// Checks if the intervals overlap or sit right next to each other.
+    pub fn touches(&self, other: &Self) -> bool {
+        self.overlaps(other)
+            || self.end.checked_add(T::ONE) == Some(other.start)
+            || other.end.checked_add(T::ONE) == Some(self.start)
+    }
+
+    /// Merges both intervals into one, if they overlap or are adjacent.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        self.touches(other)
+            .then(|| Self::new(self.start.min(other.start), self.end.max(other.end)))
+    }
+
+    /// Removes the points of `other` from the interval, which leaves between zero and two pieces.
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        let Some(shared) = self.intersection(other) else {
+            return vec![*self];
+        };
+
+        // Stepping past the shared points only fails when there is nothing left on that side.
+        let before = shared
+            .start
+            .checked_sub(T::ONE)
+            .filter(|&end| self.start <= end)
+            .map(|end| Self::new(self.start, end));
+        let after = shared
+            .end
+            .checked_add(T::ONE)
+            .filter(|&start| start <= self.end)
+            .map(|start| Self::new(start, self.end));
+
+        before.into_iter().chain(after).collect()
+    }
+}
+
+/// Set of points stored as sorted intervals, where overlapping or adjacent
+/// intervals are merged together as they are inserted.
+// Imported on its own next to `Interval`, where a bare `Set` would say nothing.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    /// Creates a new, empty `IntervalSet`.
+    pub const fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    /// Adds an interval to the set, merging it with every interval it touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        // The stored intervals are sorted and never touch each other,
+        // so the ones touching the new interval are all next to each other.
+        let first = self.intervals.partition_point(|existing| {
+            existing.end < interval.start && !existing.touches(&interval)
+        });
+        let last = first
+            + self.intervals[first..]
+                .iter()
+                .take_while(|existing| existing.touches(&interval))
+                .count();
+
+        let merged = self
+            .intervals
+            .drain(first..last)
+            .fold(interval, |merged, existing| {
+                Interval::new(
+                    merged.start.min(existing.start),
+                    merged.end.max(existing.end),
+                )
+            });
+        self.intervals.insert(first, merged);
+    }
+
+    /// Removes the points of `interval` from the set.
+    pub fn remove(&mut self, interval: &Interval<T>) {
+        self.intervals = self
+            .intervals
+            .iter()
+            .flat_map(|existing| existing.difference(interval))
+            .collect();
+    }
+
+    /// Checks if `point` is covered by the set.
+    pub fn contains_point(&self, point: T) -> bool {
+        let idx = self
+            .intervals
+            .partition_point(|existing| existing.end < point);
+
+        self.intervals
+            .get(idx)
+            .is_some_and(|existing| existing.contains_point(point))
+    }
+
+    /// Total amount of points covered by the set, or `None` if that amount doesn't fit in `T`.
+    pub fn length(&self) -> Option<T> {
+        self.intervals.iter().try_fold(T::ZERO, |total, interval| {
+            total.checked_add(interval.length()?)
+        })
+    }
+
+    /// Iterates through the merged intervals from lowest to highest.
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+}
+
+/// Sorts the intervals once and merges them in a single pass, rather than inserting them one by one.
+impl<T: Discrete> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut sorted = iter.into_iter().collect::<Vec<_>>();
+        sorted.sort_unstable_by_key(|interval| interval.start);
+
+        let mut intervals: Vec<Interval<T>> = Vec::with_capacity(sorted.len());
+
+        for interval in sorted {
+            match intervals.last_mut() {
+                // Sorting by start means the interval can only touch the last one kept.
+                Some(last) if last.touches(&interval) => last.end = last.end.max(interval.end),
+                _ => intervals.push(interval),
+            }
+        }
+
+        Self { intervals }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals() {
+        let a = Interval::new(2, 6);
+        let b = Interval::new(4, 8);
+        let c = Interval::new(7, 9);
+
+        assert!(a.contains(&Interval::new(3, 5)) && !a.contains(&b));
+        assert!(a.overlaps(&b) && !a.overlaps(&c));
+        assert_eq!(Some(Interval::new(4, 6)), a.intersection(&b));
+        assert_eq!(None, a.intersection(&c));
+        assert_eq!(Some(Interval::new(2, 9)), a.union(&c));
+        assert_eq!(None, a.union(&Interval::new(8, 9)));
+        assert_eq!(Some(5), a.length());
+
+        assert_eq!(vec![Interval::new(2, 3)], a.difference(&b));
+        assert_eq!(
+            vec![Interval::new(2, 3), Interval::new(6, 6)],
+            a.difference(&Interval::new(4, 5))
+        );
+        assert!(a.difference(&Interval::new(0, 10)).is_empty());
+        assert_eq!(vec![a], a.difference(&c));
+    }
+
+    #[test]
+    fn interval_sets() {
+        let mut set = [(10, 12), (1, 2), (5, 6), (3, 3)]
+            .into_iter()
+            .map(|(start, end)| Interval::new(start, end))
+            .collect::<IntervalSet<u32>>();
+
+        assert_eq!(
+            vec![
+                &Interval::new(1, 3),
+                &Interval::new(5, 6),
+                &Interval::new(10, 12)
+            ],
+            set.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(8), set.length());
+
+        // Bridging a gap merges the intervals on both sides of it.
+        set.insert(Interval::new(7, 9));
+        assert_eq!(
+            vec![&Interval::new(1, 3), &Interval::new(5, 12)],
+            set.iter().collect::<Vec<_>>()
+        );
+
+        set.remove(&Interval::new(4, 5));
+        assert!(set.contains_point(3) && !set.contains_point(4) && set.contains_point(6));
+        assert!(!set.contains_point(0) && !set.contains_point(13));
+        assert_eq!(Some(10), set.length());
+
+        // Overlapping intervals given out of order are merged when collecting too.
+        let collected = [(5, 8), (1, 3), (20, 25), (4, 4), (7, 10)]
+            .into_iter()
+            .map(|(start, end)| Interval::new(start, end))
+            .collect::<IntervalSet<i32>>();
+        assert_eq!(
+            vec![&Interval::new(1, 10), &Interval::new(20, 25)],
+            collected.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn bounds_of_the_type() {
+        let (low, high) = (Interval::new(0_u8, 3), Interval::new(250_u8, 255));
+
+        assert!(!high.touches(&low) && !low.touches(&high));
+        assert_eq!(None, low.union(&high));
+        assert_eq!(
+            Some(Interval::new(100, 255)),
+            high.union(&Interval::new(100, 249))
+        );
+        assert_eq!(None, Interval::new(0_u8, 255).length());
+        assert_eq!(Some(6), high.length());
+        assert_eq!(
+            vec![Interval::new(250, 254)],
+            high.difference(&Interval::new(255, 255))
+        );
+        assert_eq!(
+            vec![Interval::new(1, 3)],
+            low.difference(&Interval::new(0, 0))
+        );
+
+        let mut set = IntervalSet::new();
+        set.insert(high);
+        set.insert(Interval::new(0, 249));
+        assert_eq!(vec![&Interval::new(0, 255)], set.iter().collect::<Vec<_>>());
+        assert_eq!(None, set.length());
+    }
+}
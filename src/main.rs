@@ -1,5 +1,28 @@
 use std::env::{self};
 
+mod aoc;
+
+#[allow(dead_code)]
+mod geometry;
+
+#[allow(dead_code)]
+mod grid;
+
+mod input;
+
+#[allow(dead_code)]
+mod interval;
+
+#[allow(dead_code)]
+mod parse;
+
+mod scaffold;
+
+#[allow(dead_code)]
+mod search;
+
+mod solutions;
+
 mod year2021;
 mod year2022;
 mod year2023;
@@ -12,12 +35,16 @@ fn main() {
             println!("No year name specified, defaulting to year2022.");
             "year2022"
         },
-        |module| module,
+        String::as_str,
     );
 
     match module {
+        "year2021" => year2021::select_day(&args),
         "year2022" => year2022::select_day(&args),
         "year2023" => year2023::select_day(&args),
+        "fetch" => aoc::fetch(&args),
+        "new" => scaffold::main(&args),
+        "submit" => aoc::submit(&args),
         _ => println!("Invalid module name."),
     }
 }
@@ -0,0 +1,312 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use crate::input;
+
+/// An error met while parsing, pointing at where it happened in the input.
+/// * `line` - Line of the input the error is on, counting from 1.
+/// * `column` - Character of the line the error starts at, counting from 1.
+/// * `message` - What went wrong.
+// Imported unqualified by the days, next to their own errors, so a bare `Error` would be
+// ambiguous there.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Creates an error at the very start of the text being parsed.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            line: 1,
+            column: 1,
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error `offset` bytes into `text`.
+    pub fn at(text: &str, offset: usize, message: impl Into<String>) -> Self {
+        Self::new(message).shift(text, offset)
+    }
+
+    /// Moves an error found while parsing `piece` to where it is in `text`,
+    /// panics if `piece` isn't a slice of `text`.
+    #[must_use]
+    pub fn within(self, text: &str, piece: &str) -> Self {
+        let offset = (piece.as_ptr() as usize).wrapping_sub(text.as_ptr() as usize);
+
+        assert!(
+            offset + piece.len() <= text.len(),
+            "'{piece}' is not a slice of '{text}'."
+        );
+
+        self.shift(text, offset)
+    }
+
+    /// Adds the lines and columns before `offset` in `text` to the position.
+    fn shift(mut self, text: &str, offset: usize) -> Self {
+        let before = &text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+
+        // Columns only add up while the error is still on the first line of the piece.
+        if self.line == 1 {
+            self.column += before[line_start..].chars().count();
+        }
+
+        self.line += before.matches('\n').count();
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// Parses an integer (or anything else implementing `FromStr`), ignoring surrounding whitespace.
+pub fn integer<T>(text: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    let trimmed = text.trim();
+
+    trimmed.parse().map_err(|err| {
+        ParseError::new(format!("Could not parse '{trimmed}': {err}")).within(text, trimmed)
+    })
+}
+
+/// Parses an integer that may be written after `prefix`, like `year2023` or just `2023`.
+pub fn prefixed_integer<T>(text: &str, prefix: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    let number = text.strip_prefix(prefix).unwrap_or(text);
+    integer(number).map_err(|err| err.within(text, number))
+}
+
+/// Parses every piece of `text` between the separators with `item`.
+pub fn list<'a, T>(
+    text: &'a str,
+    separator: &str,
+    mut item: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    text.split(separator)
+        .map(|piece| item(piece).map_err(|err| err.within(text, piece)))
+        .collect()
+}
+
+/// Splits `text` around the first separator, and parses each side.
+pub fn pair<'a, A, B>(
+    text: &'a str,
+    separator: &str,
+    left: impl FnOnce(&'a str) -> Result<A, ParseError>,
+    right: impl FnOnce(&'a str) -> Result<B, ParseError>,
+) -> Result<(A, B), ParseError> {
+    let (first, second) = text
+        .split_once(separator)
+        .ok_or_else(|| ParseError::new(format!("Expected '{separator}' in '{text}'")))?;
+
+    Ok((
+        left(first).map_err(|err| err.within(text, first))?,
+        right(second).map_err(|err| err.within(text, second))?,
+    ))
+}
+
+/// Parses a line like `key: value`, giving back the trimmed key and the parsed value.
+pub fn key_value<'a, V>(
+    text: &'a str,
+    value: impl FnOnce(&'a str) -> Result<V, ParseError>,
+) -> Result<(&'a str, V), ParseError> {
+    pair(
+        text,
+        ":",
+        |key| match key.trim() {
+            "" => Err(ParseError::new("Expected a key before ':'")),
+            trimmed => Ok(trimmed),
+        },
+        |rest| {
+            let trimmed = rest.trim();
+            value(trimmed).map_err(|err| err.within(rest, trimmed))
+        },
+    )
+}
+
+/// Parses every line of `text` with `line`.
+pub fn lines<'a, T>(
+    text: &'a str,
+    mut line: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    text.lines()
+        .map(|piece| line(piece).map_err(|err| err.within(text, piece)))
+        .collect()
+}
+
+/// Parses every paragraph of normalised `text` with `paragraph`, see [`input::paragraphs`].
+pub fn paragraphs<'a, T>(
+    text: &'a str,
+    mut paragraph: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input::paragraphs(text)
+        .map(|piece| paragraph(piece).map_err(|err| err.within(text, piece)))
+        .collect()
+}
+
+/// Matches `text` against a template like `move {} from {} to {}`, giving back
+/// the text in place of each `{}`. Placeholders need some literal text between them.
+pub fn template<'a>(text: &'a str, template: &str) -> Result<Vec<&'a str>, ParseError> {
+    let offset = |rest: &str| text.len() - rest.len();
+    let mut literals = template.split("{}");
+
+    // Splitting always gives at least one piece, even for an empty template.
+    let prefix = literals.next().unwrap_or_default();
+    let mut rest = text
+        .strip_prefix(prefix)
+        .ok_or_else(|| ParseError::new(format!("Expected '{prefix}'")))?;
+
+    let mut captures = vec![];
+
+    for literal in literals {
+        let end = if literal.is_empty() {
+            rest.len()
+        } else {
+            rest.find(literal).ok_or_else(|| {
+                ParseError::at(
+                    text,
+                    offset(rest),
+                    format!("Expected '{literal}' after this"),
+                )
+            })?
+        };
+
+        if end == 0 {
+            return Err(ParseError::at(text, offset(rest), "Expected a value"));
+        }
+
+        captures.push(&rest[..end]);
+        rest = &rest[end + literal.len()..];
+    }
+
+    if rest.is_empty() {
+        Ok(captures)
+    } else {
+        Err(ParseError::at(
+            text,
+            offset(rest),
+            format!("Unexpected '{rest}' at the end"),
+        ))
+    }
+}
+
+/// Matches `text` against a template with `N` placeholders, see [`template`],
+/// and parses each of them as an integer.
+pub fn integers<T, const N: usize>(text: &str, template: &str) -> Result<[T; N], ParseError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    assert_eq!(
+        template.matches("{}").count(),
+        N,
+        "Template '{template}' must have exactly {N} placeholders."
+    );
+
+    let values = self::template(text, template)?
+        .into_iter()
+        .map(|capture| integer(capture).map_err(|err| err.within(text, capture)))
+        .collect::<Result<Vec<T>, _>>()?;
+
+    // The template was checked to have exactly `N` placeholders.
+    let Ok(values) = values.try_into() else {
+        unreachable!("Template '{template}' gave the wrong amount of values.");
+    };
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_and_lists() {
+        assert_eq!(Ok(42), integer::<u32>(" 42 "));
+        assert_eq!(Ok(-7), integer::<i32>("-7"));
+        assert_eq!(Ok(2023), prefixed_integer::<u32>("year2023", "year"));
+        assert_eq!(Ok(7), prefixed_integer::<u32>("7", "day"));
+        assert!(prefixed_integer::<u32>("dayx", "day").is_err());
+        assert_eq!(Ok(vec![7, 4, 9]), list("7,4,9", ",", integer::<u8>));
+
+        // The position points at the piece that failed, not the whole list.
+        let err = list("1, 2,x3", ",", integer::<u8>).unwrap_err();
+        assert_eq!((1, 6), (err.line, err.column));
+        assert!(err.message.starts_with("Could not parse 'x3'"));
+    }
+
+    #[test]
+    fn pairs_and_key_values() {
+        assert_eq!(Ok(("up", 3)), pair("up 3", " ", Ok, integer::<u8>));
+        assert_eq!(
+            Ok(("Game 4", vec![1, 2])),
+            key_value("Game 4: 1, 2", |value| list(value, ",", integer::<u8>))
+        );
+
+        let err = pair("2-4", ",", Ok, Ok).unwrap_err();
+        assert_eq!("line 1, column 1: Expected ',' in '2-4'", err.to_string());
+
+        let err = key_value("seeds:  1 b", |value| list(value, " ", integer::<u8>)).unwrap_err();
+        assert_eq!((1, 11), (err.line, err.column));
+        assert!(key_value(" : 1", integer::<u8>).is_err());
+    }
+
+    #[test]
+    fn lines_and_paragraphs() {
+        assert_eq!(
+            Ok(vec![vec![1, 2], vec![3]]),
+            paragraphs("1\n2\n\n3", |paragraph| lines(paragraph, integer::<u8>))
+        );
+
+        // Lines add up across paragraphs, and columns stay within the line.
+        let err = paragraphs("1\n2\n\n3\n 4x", |paragraph| {
+            lines(paragraph, integer::<u8>)
+        })
+        .unwrap_err();
+        assert_eq!((5, 2), (err.line, err.column));
+    }
+
+    #[test]
+    fn templates() {
+        assert_eq!(
+            Ok(vec!["1", "2", "3"]),
+            template("move 1 from 2 to 3", "move {} from {} to {}")
+        );
+        assert_eq!(
+            Ok([12, 7, 1]),
+            integers::<usize, 3>("move 12 from 7 to 1", "move {} from {} to {}")
+        );
+        assert_eq!(Ok(vec!["x=1"]), template("<x=1>", "<{}>"));
+
+        let err = template("move 1 to 3", "move {} from {} to {}").unwrap_err();
+        assert_eq!((1, 6), (err.line, err.column));
+
+        let err = template("move  from 2 to 3", "move {} from {} to {}").unwrap_err();
+        assert_eq!("line 1, column 6: Expected a value", err.to_string());
+
+        let err = template("move 1 from 2 to 3!", "move {} from {} to {}!!").unwrap_err();
+        assert_eq!((1, 18), (err.line, err.column));
+
+        let err = integers::<u8, 2>("1,-2", "{},{}").unwrap_err();
+        assert_eq!((1, 3), (err.line, err.column));
+    }
+}
@@ -0,0 +1,442 @@
+use crate::parse;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+// Templates for a new day, and for the year holding it if that is new too.
+const DAY_TEMPLATE: &str = include_str!("day.txt");
+const YEAR_TEMPLATE: &str = include_str!("year.txt");
+
+/// Creates the module of a new day from `template new <year> <day> [--root <dir>]`,
+/// in the crate at `--root`, or the current directory without one.
+pub fn main(args: &[String]) {
+    let (Some(year), Some(day)) = (args.get(2), args.get(3)) else {
+        println!("Usage: template new <year> <day> [--root <dir>], like 'template new 2023 3'.");
+        return;
+    };
+
+    match find_src(args)
+        .and_then(|src| parse_number(year, "year").map(|year| (src, year)))
+        .and_then(|(src, year)| parse_number(day, "day").and_then(|day| new_day(&src, year, day)))
+    {
+        Ok(created) => {
+            for path in created {
+                println!("Created {}", path.display());
+            }
+        }
+        Err(err) => println!("Could not create the day: {err}"),
+    }
+}
+
+/// Finds the `src` directory of the crate to scaffold in, which is under `--root`
+/// if it was given, or under the directory the command is run from otherwise.
+fn find_src(args: &[String]) -> Result<PathBuf, String> {
+    let root = match args.iter().position(|arg| arg == "--root") {
+        Some(idx) => args
+            .get(idx + 1)
+            .map(PathBuf::from)
+            .ok_or("--root needs the directory of the crate after it!")?,
+        None => env::current_dir()
+            .map_err(|err| format!("Could not find the current directory: {err}"))?,
+    };
+
+    let src = root.join("src");
+
+    if src.join("main.rs").is_file() {
+        Ok(src)
+    } else {
+        Err(format!(
+            "{} has no src/main.rs, run this from the crate root or pass --root <dir>!",
+            root.display()
+        ))
+    }
+}
+
+/// Parses a number like `2023`, also allowing it to be written like `year2023`.
+fn parse_number(arg: &str, prefix: &str) -> Result<u32, String> {
+    parse::prefixed_integer(arg, prefix).map_err(|err| format!("Invalid {prefix} '{arg}': {err}"))
+}
+
+/// Generates `year<year>/day<day>` under `src` with an empty example input, and registers
+/// it and its solver in its year, creating and registering the year first if needed.
+/// The new files are written before any module list is changed, and everything
+/// is undone if a step fails. Gives back every file that was created.
+fn new_day(src: &Path, year: u32, day: u32) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("Day {day} is not between 1 and 25!"));
+    }
+
+    let (year_name, day_name) = (format!("year{year}"), format!("day{day}"));
+    let year_dir = src.join(&year_name);
+    let day_dir = year_dir.join(&day_name);
+
+    if day_dir.exists() {
+        return Err(format!("{} already exists!", day_dir.display()));
+    }
+
+    // Every change is worked out up front, so nothing is written if any of them fails.
+    let year_mod = year_dir.join("mod.rs");
+    let new_year = !year_mod.exists();
+    let mut patches = vec![];
+
+    let year_source = if new_year {
+        let main_rs = src.join("main.rs");
+        let main_source = read(&main_rs)?;
+
+        let main_patched = add_mod(&main_source, &year_name, "mod year")?;
+        let main_patched = add_arm(
+            &main_patched,
+            "main",
+            &format!("\"{year_name}\" => {year_name}::select_day(&args),"),
+        )?;
+
+        let solutions_rs = src.join("solutions.rs");
+        let solutions_source = read(&solutions_rs)?;
+        let solutions_patched = add_arm(
+            &solutions_source,
+            "solver",
+            &format!("{year} => crate::{year_name}::solver(day),"),
+        )?;
+
+        patches.push(Patch::new(main_rs, main_source, main_patched));
+        patches.push(Patch::new(
+            solutions_rs,
+            solutions_source,
+            solutions_patched,
+        ));
+        YEAR_TEMPLATE.to_string()
+    } else {
+        read(&year_mod)?
+    };
+
+    let year_patched = add_mod(&year_source, &day_name, "mod day")?;
+    let year_patched = add_arm(
+        &year_patched,
+        "select_day",
+        &format!("\"{day_name}\" => {day_name}::main(),"),
+    )?;
+    let year_patched = add_arm(
+        &year_patched,
+        "solver",
+        &format!("{day} => Some({day_name}::solve),"),
+    )?;
+
+    let mut files = vec![];
+
+    if new_year {
+        files.push((year_mod, year_patched));
+    } else {
+        patches.push(Patch::new(year_mod, year_source, year_patched));
+    }
+
+    // The real input isn't kept with the code, the day fetches it by its year and day.
+    let day_source = DAY_TEMPLATE
+        .replace("__YEAR__", &year.to_string())
+        .replace("__DAY__", &day.to_string());
+
+    files.push((day_dir.join("mod.rs"), day_source));
+    files.push((day_dir.join("input-ex.txt"), String::new()));
+
+    // Only the directory that didn't exist before has to go if anything fails.
+    let new_dir = if new_year { &year_dir } else { &day_dir };
+
+    let result = create_dir(&day_dir)
+        .and_then(|()| {
+            files
+                .iter()
+                .try_for_each(|(path, contents)| write(path, contents))
+        })
+        .and_then(|()| apply(&patches));
+
+    if let Err(err) = result {
+        // The first error is the one worth reporting, whether or not this works.
+        let _ = fs::remove_dir_all(new_dir);
+        return Err(err);
+    }
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+/// A change to a file that already exists.
+/// * `path` - The file being changed.
+/// * `original` - What the file held before, to put back if something fails.
+/// * `patched` - What the file should hold.
+struct Patch {
+    path: PathBuf,
+    original: String,
+    patched: String,
+}
+
+impl Patch {
+    const fn new(path: PathBuf, original: String, patched: String) -> Self {
+        Self {
+            path,
+            original,
+            patched,
+        }
+    }
+}
+
+/// Writes every patch, putting the files that were already patched back if one fails.
+fn apply(patches: &[Patch]) -> Result<(), String> {
+    for (idx, patch) in patches.iter().enumerate() {
+        if let Err(err) = write(&patch.path, &patch.patched) {
+            for applied in &patches[..idx] {
+                // The first error is the one worth reporting, whether or not this works.
+                let _ = write(&applied.path, &applied.original);
+            }
+
+            return Err(err);
+        }
+    }
+
+    Ok(())
+}
+
+/// Declares module `name` after the last line starting with `prefix`,
+/// or at the very top of `source` if there is none.
+fn add_mod(source: &str, name: &str, prefix: &str) -> Result<String, String> {
+    let declaration = format!("mod {name};");
+
+    if source.lines().any(|line| line.trim() == declaration) {
+        return Err(format!("'{declaration}' is already declared!"));
+    }
+
+    let mut lines = source.lines().collect::<Vec<_>>();
+    let idx = lines
+        .iter()
+        .rposition(|line| line.starts_with(prefix))
+        .map_or(0, |idx| idx + 1);
+
+    // A module on its own at the top still needs a blank line before the code after it.
+    let declaration = if idx == 0 {
+        format!("{declaration}\n")
+    } else {
+        declaration
+    };
+
+    lines.insert(idx, &declaration);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds `arm` to the match in function `function` of `source`, right before its catch-all arm.
+fn add_arm(source: &str, function: &str, arm: &str) -> Result<String, String> {
+    let signature = format!("fn {function}(");
+    let mut lines = source.lines().collect::<Vec<_>>();
+    let idx = lines
+        .iter()
+        .position(|line| line.contains(&signature))
+        .and_then(|start| {
+            lines[start..]
+                .iter()
+                .position(|line| line.trim_start().starts_with("_ =>"))
+                .map(|idx| start + idx)
+        })
+        .ok_or_else(|| format!("Could not find where to add '{arm}'!"))?;
+
+    let indent = &lines[idx][..lines[idx].len() - lines[idx].trim_start().len()];
+    let arm = format!("{indent}{arm}");
+
+    lines.insert(idx, &arm);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("Could not read {}: {err}", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|err| format!("Could not write {}: {err}", path.display()))
+}
+
+fn create_dir(path: &Path) -> Result<(), String> {
+    fs::create_dir_all(path).map_err(|err| format!("Could not create {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::temp_dir;
+
+    // Cut down versions of the files days and years get registered in. These are fixed,
+    // so the tests don't depend on which days the crate itself has.
+    const MAIN_FIXTURE: &str = "\
+mod aoc;
+mod year2021;
+mod year2022;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    match args.get(1).map_or(\"year2022\", String::as_str) {
+        \"year2021\" => year2021::select_day(&args),
+        \"year2022\" => year2022::select_day(&args),
+        \"fetch\" => aoc::fetch(&args),
+        _ => println!(\"Invalid module name.\"),
+    }
+}
+";
+
+    const YEAR_FIXTURE: &str = "\
+mod day1;
+mod day2;
+
+use crate::solutions::Solver;
+
+pub fn select_day(args: &[String]) {
+    match args.get(2).map_or(\"day1\", String::as_str) {
+        \"day1\" => day1::main(),
+        \"day2\" => day2::main(args),
+        _ => println!(\"Invalid module name.\"),
+    }
+}
+
+pub fn solver(day: u32) -> Option<Solver> {
+    match day {
+        1 => Some(day1::solve),
+        2 => Some(day2::solve),
+        _ => None,
+    }
+}
+";
+
+    const SOLUTIONS_FIXTURE: &str = "\
+pub fn solver(year: u32, day: u32) -> Option<Solver> {
+    match year {
+        2021 => crate::year2021::solver(day),
+        2022 => crate::year2022::solver(day),
+        _ => None,
+    }
+}
+";
+
+    /// Writes the registration parts of a crate into a fresh directory to scaffold in.
+    fn fake_src(name: &str) -> PathBuf {
+        let src = temp_dir(&format!("scaffold-{name}"));
+
+        fs::create_dir_all(src.join("year2021")).unwrap();
+        fs::write(src.join("main.rs"), MAIN_FIXTURE).unwrap();
+        fs::write(src.join("solutions.rs"), SOLUTIONS_FIXTURE).unwrap();
+        fs::write(src.join("year2021/mod.rs"), YEAR_FIXTURE).unwrap();
+
+        src
+    }
+
+    #[test]
+    fn existing_year() {
+        let src = fake_src("existing");
+
+        let created = new_day(&src, 2021, 6).unwrap();
+        assert_eq!(2, created.len());
+
+        let day_source = fs::read_to_string(src.join("year2021/day6/mod.rs")).unwrap();
+        assert!(day_source.contains("aoc::puzzle_input(2021, 6)"));
+        assert!(!day_source.contains("__"));
+        assert!(day_source.contains("pub fn solve(_input: &str) -> Answers {\n    [None, None]\n}"));
+        assert!(fs::read_to_string(src.join("year2021/day6/input-ex.txt"))
+            .unwrap()
+            .is_empty());
+        assert!(!src.join("year2021/day6/input.txt").exists());
+
+        let year_source = fs::read_to_string(src.join("year2021/mod.rs")).unwrap();
+        assert!(year_source.contains("mod day2;\nmod day6;\n"));
+        assert!(year_source.contains(
+            "        \"day6\" => day6::main(),\n        _ => println!(\"Invalid module name.\"),"
+        ));
+        assert!(year_source.contains("        6 => Some(day6::solve),\n        _ => None,"));
+
+        // The main and solutions files are left alone, and days can't be created twice.
+        assert_eq!(
+            MAIN_FIXTURE,
+            fs::read_to_string(src.join("main.rs")).unwrap()
+        );
+        assert_eq!(
+            SOLUTIONS_FIXTURE,
+            fs::read_to_string(src.join("solutions.rs")).unwrap()
+        );
+        assert!(new_day(&src, 2021, 6).is_err());
+        assert!(new_day(&src, 2021, 26).is_err());
+
+        fs::remove_dir_all(src).unwrap();
+    }
+
+    #[test]
+    fn new_year() {
+        let src = fake_src("new");
+
+        new_day(&src, 2099, 1).unwrap();
+
+        let main_source = fs::read_to_string(src.join("main.rs")).unwrap();
+        assert!(main_source.contains("mod year2022;\nmod year2099;\n"));
+        assert!(main_source
+            .contains("        \"year2099\" => year2099::select_day(&args),\n        _ =>"));
+
+        let solutions_source = fs::read_to_string(src.join("solutions.rs")).unwrap();
+        assert!(solutions_source
+            .contains("        2099 => crate::year2099::solver(day),\n        _ => None,"));
+
+        let year_source = fs::read_to_string(src.join("year2099/mod.rs")).unwrap();
+        assert!(year_source.starts_with("mod day1;\n\nuse crate::solutions::Solver;\n"));
+        assert!(year_source.contains("\"day1\" => day1::main(),"));
+        assert!(year_source.contains("1 => Some(day1::solve),"));
+
+        fs::remove_dir_all(src).unwrap();
+    }
+
+    #[test]
+    fn failures_leave_nothing_behind() {
+        let src = fake_src("failures");
+        let main_source = fs::read_to_string(src.join("main.rs")).unwrap();
+
+        // A year without a catch-all arm can't be registered in, so nothing is created.
+        fs::create_dir_all(src.join("year2098")).unwrap();
+        fs::write(src.join("year2098/mod.rs"), "mod day1;\n").unwrap();
+        assert!(new_day(&src, 2098, 2).is_err());
+        assert!(!src.join("year2098/day2").exists());
+        assert_eq!(
+            "mod day1;\n",
+            fs::read_to_string(src.join("year2098/mod.rs")).unwrap()
+        );
+
+        // Files patched before a failing one are put back the way they were.
+        let patches = [
+            Patch::new(src.join("main.rs"), main_source.clone(), String::new()),
+            Patch::new(src.join("year2021"), String::new(), String::new()),
+        ];
+        assert!(apply(&patches).is_err());
+        assert_eq!(
+            main_source,
+            fs::read_to_string(src.join("main.rs")).unwrap()
+        );
+
+        fs::remove_dir_all(src).unwrap();
+    }
+
+    #[test]
+    fn finding_the_crate() {
+        let root = fake_src("root");
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/main.rs"), "").unwrap();
+
+        let args = |extra: &[&Path]| {
+            let mut args = ["template", "new", "2023", "3"].map(String::from).to_vec();
+            args.extend(extra.iter().map(|arg| arg.display().to_string()));
+            args
+        };
+
+        // Tests run from the crate root, which has a src/main.rs of its own.
+        assert_eq!(
+            env::current_dir().unwrap().join("src"),
+            find_src(&args(&[])).unwrap()
+        );
+        assert_eq!(
+            root.join("src"),
+            find_src(&args(&[Path::new("--root"), &root])).unwrap()
+        );
+        assert!(find_src(&args(&[Path::new("--root"), &root.join("year2021")])).is_err());
+        assert!(find_src(&args(&[Path::new("--root")])).is_err());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,298 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// Cost of moving along an edge, or of a whole path.
+pub type Cost = u64;
+
+/// Anything that can list the neighbours of a node, along with the cost of moving to them.
+/// Closures like `|node: &N| -> impl IntoIterator<Item = (N, Cost)>` are graphs already.
+pub trait Graph<N> {
+    fn neighbours(&self, node: &N) -> Vec<(N, Cost)>;
+}
+
+impl<N, F, I> Graph<N> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, Cost)>,
+{
+    fn neighbours(&self, node: &N) -> Vec<(N, Cost)> {
+        self(node).into_iter().collect()
+    }
+}
+
+/// A path found by a search.
+/// * `nodes` - Every node along the path, from the start to the goal.
+/// * `cost` - Total cost of the edges taken, which is the amount of steps for `bfs`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Path<N> {
+    pub nodes: Vec<N>,
+    pub cost: Cost,
+}
+
+/// Every node seen during a search, stored once and referred to by index afterwards.
+/// * `nodes` - The nodes in the order they were seen.
+/// * `parents` - Index of the node each node was reached from, `None` for the start.
+/// * `costs` - Cheapest known cost of reaching each node.
+/// * `seen` - Index of each node, so visited states are found by their hash.
+struct Visited<N> {
+    nodes: Vec<N>,
+    parents: Vec<Option<usize>>,
+    costs: Vec<Cost>,
+    seen: HashMap<N, usize>,
+}
+
+impl<N: Clone + Eq + Hash> Visited<N> {
+    fn new(start: &N) -> Self {
+        Self {
+            nodes: vec![start.clone()],
+            parents: vec![None],
+            costs: vec![0],
+            seen: HashMap::from([(start.clone(), 0)]),
+        }
+    }
+
+    /// Records reaching `node` from `parent` at `cost`, giving back its index
+    /// unless it was already reached as cheaply.
+    fn reach(&mut self, node: N, parent: usize, cost: Cost) -> Option<usize> {
+        if let Some(&idx) = self.seen.get(&node) {
+            if self.costs[idx] <= cost {
+                return None;
+            }
+
+            self.parents[idx] = Some(parent);
+            self.costs[idx] = cost;
+            return Some(idx);
+        }
+
+        let idx = self.nodes.len();
+        self.seen.insert(node.clone(), idx);
+        self.nodes.push(node);
+        self.parents.push(Some(parent));
+        self.costs.push(cost);
+        Some(idx)
+    }
+
+    /// Follows the parents back from `goal` to put together the path to it.
+    fn path_to(&self, goal: usize) -> Path<N> {
+        let mut nodes = std::iter::successors(Some(goal), |&idx| self.parents[idx])
+            .map(|idx| self.nodes[idx].clone())
+            .collect::<Vec<_>>();
+
+        nodes.reverse();
+
+        Path {
+            nodes,
+            cost: self.costs[goal],
+        }
+    }
+}
+
+/// Finds the path with the fewest steps from `start` to a node passing `is_goal`,
+/// ignoring the cost of the edges.
+pub fn bfs<N, G>(graph: &G, start: &N, mut is_goal: impl FnMut(&N) -> bool) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N> + ?Sized,
+{
+    let mut visited = Visited::new(start);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(idx) = queue.pop_front() {
+        if is_goal(&visited.nodes[idx]) {
+            return Some(visited.path_to(idx));
+        }
+
+        let steps = visited.costs[idx] + 1;
+
+        for (next, _) in graph.neighbours(&visited.nodes[idx]) {
+            // Nodes are first reached along a shortest path, so later visits are never cheaper.
+            if !visited.seen.contains_key(&next) {
+                queue.extend(visited.reach(next, idx, steps));
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds the cheapest path from `start` to a node passing `is_goal`.
+pub fn dijkstra<N, G>(graph: &G, start: &N, is_goal: impl FnMut(&N) -> bool) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N> + ?Sized,
+{
+    astar(graph, start, is_goal, |_| 0)
+}
+
+/// Finds the cheapest path from `start` to a node passing `is_goal`, exploring the nodes
+/// that `heuristic` guesses are closest to a goal first. The heuristic must never
+/// guess more than the actual remaining cost, or a more expensive path may be found.
+pub fn astar<N, G>(
+    graph: &G,
+    start: &N,
+    mut is_goal: impl FnMut(&N) -> bool,
+    heuristic: impl Fn(&N) -> Cost,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N> + ?Sized,
+{
+    let mut visited = Visited::new(start);
+    let mut frontier = BinaryHeap::from([Reverse((heuristic(start), 0, 0))]);
+
+    while let Some(Reverse((_, cost, idx))) = frontier.pop() {
+        // A cheaper way to this node was found after this entry was added.
+        if cost > visited.costs[idx] {
+            continue;
+        }
+
+        if is_goal(&visited.nodes[idx]) {
+            return Some(visited.path_to(idx));
+        }
+
+        for (next, step) in graph.neighbours(&visited.nodes[idx]) {
+            let next_cost = cost + step;
+
+            if let Some(next_idx) = visited.reach(next, idx, next_cost) {
+                let guess = next_cost + heuristic(&visited.nodes[next_idx]);
+                frontier.push(Reverse((guess, next_cost, next_idx)));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Grid, Position};
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#...
+.##...#E";
+
+    const RISKS: &str = "\
+1163751
+1381373
+2136511
+3694931
+7463417";
+
+    /// Moves to the open cells next to a position in a maze.
+    fn open_cells(maze: &Grid<char>) -> impl Fn(&Position) -> Vec<(Position, Cost)> + '_ {
+        |&position| {
+            maze.neighbours4(position)
+                .filter(|&next| maze[next] != '#')
+                .map(|next| (next, 1))
+                .collect()
+        }
+    }
+
+    fn find(maze: &Grid<char>, target: char) -> Position {
+        maze.positions().find(|&at| maze[at] == target).unwrap()
+    }
+
+    #[test]
+    fn breadth_first() {
+        let maze = Grid::parse(MAZE, Ok).unwrap();
+        let (start, end) = (find(&maze, 'S'), find(&maze, 'E'));
+
+        let path = bfs(&open_cells(&maze), &start, |&at| at == end).unwrap();
+        assert_eq!(12, path.cost);
+        assert_eq!(13, path.nodes.len());
+        assert_eq!(
+            (Some(&start), Some(&end)),
+            (path.nodes.first(), path.nodes.last())
+        );
+
+        // Every step of the path moves to a neighbouring open cell.
+        assert!(path
+            .nodes
+            .windows(2)
+            .all(|pair| open_cells(&maze)(&pair[0]).contains(&(pair[1], 1))));
+
+        let walled_off = Grid::parse("S#E", Ok).unwrap();
+        assert_eq!(
+            None,
+            bfs(&open_cells(&walled_off), &(0, 0), |&at| at == (2, 0))
+        );
+    }
+
+    #[test]
+    fn cheapest_paths() {
+        let risks = Grid::parse(RISKS, |char| {
+            char.to_digit(10)
+                .ok_or_else(|| format!("{char} is not a digit"))
+        })
+        .unwrap();
+        let end = (risks.width() - 1, risks.height() - 1);
+
+        // Entering a cell costs its risk.
+        let graph = |&position: &Position| {
+            risks
+                .neighbours4(position)
+                .map(|next| (next, Cost::from(risks[next])))
+                .collect::<Vec<_>>()
+        };
+
+        let cheapest = dijkstra(&graph, &(0, 0), |&at| at == end).unwrap();
+        assert_eq!(28, cheapest.cost);
+        assert_eq!(
+            cheapest.cost,
+            cheapest.nodes[1..]
+                .iter()
+                .map(|&at| Cost::from(risks[at]))
+                .sum()
+        );
+
+        // Every cell costs at least 1, so the manhattan distance never overestimates.
+        let manhattan = |&(x, y): &Position| (end.0 - x + end.1 - y) as Cost;
+        let guided = astar(&graph, &(0, 0), |&at| at == end, manhattan).unwrap();
+        assert_eq!(cheapest.cost, guided.cost);
+
+        // The fewest steps don't have to be the cheapest.
+        assert!(bfs(&graph, &(0, 0), |&at| at == end).unwrap().cost < cheapest.cost);
+    }
+
+    #[test]
+    fn crane_arrangements() {
+        type Stacks = Vec<String>;
+
+        // Moving one crate at a time from the top of a stack to the top of another.
+        let moves = |stacks: &Stacks| {
+            let mut next = vec![];
+
+            for from in 0..stacks.len() {
+                for to in (0..stacks.len()).filter(|&to| to != from) {
+                    let mut moved = stacks.clone();
+
+                    if let Some(item) = moved[from].pop() {
+                        moved[to].push(item);
+                        next.push((moved, 1));
+                    }
+                }
+            }
+
+            next
+        };
+
+        let stacks = |items: [&str; 3]| items.map(String::from).to_vec();
+
+        // Works back from the rearranged stacks of the example to the ones it started with.
+        let rearranged = stacks(["C", "M", "PDNZ"]);
+        let start = stacks(["ZN", "MCD", "P"]);
+
+        let path = bfs(&moves, &rearranged, |arrangement| arrangement == &start).unwrap();
+        assert_eq!(Some(&start), path.nodes.last());
+        assert_eq!(
+            path.cost,
+            dijkstra(&moves, &rearranged, |a| a == &start).unwrap().cost
+        );
+    }
+}
@@ -0,0 +1,41 @@
+// Every solved puzzle, found by its year and day so answers can be worked out
+// without going through the modules by hand.
+
+/// The answers to both parts of a puzzle, where a part that isn't solved yet has none.
+pub type Answers = [Option<String>; 2];
+
+/// Works out the answers of a puzzle from its input.
+pub type Solver = fn(&str) -> Answers;
+
+/// Finds the solver of a puzzle, if there is one.
+pub fn solver(year: u32, day: u32) -> Option<Solver> {
+    match year {
+        2021 => crate::year2021::solver(day),
+        2022 => crate::year2022::solver(day),
+        2023 => crate::year2023::solver(day),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finding_solvers() {
+        assert!(solver(2019, 1).is_none());
+        assert!(solver(2021, 6).is_none());
+
+        let solve = solver(2022, 5).unwrap();
+        assert_eq!(
+            [Some("CMZ".to_string()), Some("MCD".to_string())],
+            solve(include_str!("year2022/day5/instructions-ex.txt"))
+        );
+
+        // Parts that aren't solved yet have no answer to give.
+        assert_eq!(
+            [Some("8240".to_string()), None],
+            solver(2022, 3).unwrap()(include_str!("year2022/day3/rucksack.txt"))
+        );
+    }
+}
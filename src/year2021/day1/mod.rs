@@ -1,46 +1,170 @@
-use std::fs;
+use crate::{input, solutions::Answers};
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead},
+};
 
-fn preprocess_data(path: &str) -> Vec<i32> {
-    // Reads lines of depths as a vector of integers
-    fs::read_to_string(path)
-        .expect("Was not able to read the file.")
-        .lines()
-        .map(|depth| depth.parse().unwrap())
-        .collect()
-}
+// Holds the contents of each of the input files.
+const DEPTHS_TEST: &str = include_str!("depths-ex.txt");
+const DEPTHS_REAL: &str = include_str!("depths.txt");
 
-fn compare_depths(depths: &[i32]) -> i32 {
-    let mut increased_counter = 0;
+// The kind of change between two consecutive windows that should be tracked.
+#[derive(Clone, Copy)]
+enum Comparison {
+    Increase,
+    Decrease,
+    Unchanged,
+}
 
-    // Enumerating through depths (skipping the first value)
-    for (i, depth) in depths.iter().enumerate().skip(1) {
-        // Increase the count of the current depth is larger than the last
-        if depth > &depths[i - 1] {
-            increased_counter += 1;
+impl Comparison {
+    // Checks whether going from the previous sum to the current one matches the comparison.
+    const fn matches(self, prev: i64, current: i64) -> bool {
+        match self {
+            Self::Increase => current > prev,
+            Self::Decrease => current < prev,
+            Self::Unchanged => current == prev,
         }
     }
+}
 
-    increased_counter
+// Tracks changes between consecutive windows while only holding onto the current window,
+// so depths can be fed in one at a time from an input of any length.
+struct WindowTracker {
+    window: VecDeque<i32>,
+    window_size: usize,
+    window_sum: i64,
+    comparison: Comparison,
+    changes: usize,
 }
 
-fn compare_windows(depths: &[i32]) -> i32 {
-    let mut increased_counter = 0;
-    let window_size = 3;
+impl WindowTracker {
+    fn new(window_size: usize, comparison: Comparison) -> Self {
+        Self {
+            window: VecDeque::with_capacity(window_size + 1),
+            window_size,
+            window_sum: 0,
+            comparison,
+            changes: 0,
+        }
+    }
+
+    // Slides the window forward with the next depth, counting a change if there was
+    // already a full window to compare against. Returns whether a change was counted.
+    fn push(&mut self, depth: i32) -> bool {
+        if self.window_size == 0 {
+            return false;
+        }
+
+        let prev_window = self.window_sum;
+        let was_full = self.window.len() == self.window_size;
 
-    // Gets the sum of a window of values in the given array.
-    let get_window_sum = |depths: &[i32], i: usize| depths[i..i + window_size].iter().sum();
+        self.window.push_back(depth);
+        self.window_sum += i64::from(depth);
 
-    // Compares sums across windows instead of individual values.
-    for i in 1..depths.len() - window_size + 1 {
-        let current_window: i32 = get_window_sum(depths, i);
-        let prev_window: i32 = get_window_sum(depths, i - 1);
+        if was_full {
+            // Drops the oldest depth so the window stays at the same size.
+            if let Some(oldest) = self.window.pop_front() {
+                self.window_sum -= i64::from(oldest);
+            }
 
-        if current_window > prev_window {
-            increased_counter += 1;
+            if self.comparison.matches(prev_window, self.window_sum) {
+                self.changes += 1;
+                return true;
+            }
         }
+
+        false
+    }
+}
+
+pub fn main(args: &[String]) {
+    // Reads depths from stdin instead when asked to, like `year2021 day1 -`.
+    if let Some("-" | "stream") = args.get(3).map(String::as_str) {
+        stream(io::stdin().lock());
+        return;
+    }
+
+    let depths_test = preprocess_data(DEPTHS_TEST);
+    let depths_real = preprocess_data(DEPTHS_REAL);
+
+    println!("{}", compare_depths(&depths_test));
+    println!("{}", compare_depths(&depths_real));
+    println!("{}", compare_windows(&depths_test));
+    println!("{}", compare_windows(&depths_real));
+}
+
+// Counts the increases as depths come in, so large sensor logs can be piped through.
+fn stream<R: BufRead>(reader: R) {
+    let mut depth_tracker = WindowTracker::new(1, Comparison::Increase);
+    let mut window_tracker = WindowTracker::new(3, Comparison::Increase);
+
+    for depth in stream_depths(reader) {
+        let depth = depth.unwrap_or_else(|err| panic!("Could not read a depth: {err}"));
+
+        depth_tracker.push(depth);
+        window_tracker.push(depth);
     }
 
-    increased_counter
+    println!("{}", depth_tracker.changes);
+    println!("{}", window_tracker.changes);
+}
+
+// Counts the increases between single depths, then between windows of three.
+pub fn solve(input: &str) -> Answers {
+    let depths = preprocess_data(input);
+    [compare_depths(&depths), compare_windows(&depths)].map(|answer| Some(answer.to_string()))
+}
+
+// Lazily reads lines of depths from any buffered reader, skipping blank lines.
+fn stream_depths<R: BufRead>(reader: R) -> impl Iterator<Item = io::Result<i32>> {
+    reader
+        .lines()
+        .filter(|line| {
+            line.as_ref()
+                .map_or(true, |line| !input::normalise_line(line).is_empty())
+        })
+        .map(|line| {
+            input::normalise_line(&line?)
+                .trim_start()
+                .parse()
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+        })
+}
+
+fn preprocess_data(contents: &str) -> Vec<i32> {
+    // Reads lines of depths as a vector of integers
+    input::normalise(contents)
+        .lines()
+        .map(|depth| {
+            depth
+                .parse()
+                .unwrap_or_else(|err| panic!("Invalid depth '{depth}': {err}"))
+        })
+        .collect()
+}
+
+// Finds the starting indices of every window whose sum changed (according to the comparison)
+// from the window right before it. Sums are kept as a rolling total, so this runs in O(n).
+fn window_changes(depths: &[i32], window_size: usize, comparison: Comparison) -> Vec<usize> {
+    let mut tracker = WindowTracker::new(window_size, comparison);
+
+    // The window that changed ends at the depth that was just pushed.
+    depths
+        .iter()
+        .enumerate()
+        .filter(|&(_, &depth)| tracker.push(depth))
+        .map(|(i, _)| i + 1 - window_size)
+        .collect()
+}
+
+fn compare_depths(depths: &[i32]) -> usize {
+    // Comparing individual depths is the same as comparing windows of size 1.
+    window_changes(depths, 1, Comparison::Increase).len()
+}
+
+fn compare_windows(depths: &[i32]) -> usize {
+    // Compares sums across windows of three instead of individual values.
+    window_changes(depths, 3, Comparison::Increase).len()
 }
 
 #[cfg(test)]
@@ -49,19 +173,58 @@ mod tests {
 
     #[test]
     fn part1() {
-        let mut data = preprocess_data("src/year2021/day1/depths-ex.txt");
+        let data = preprocess_data(DEPTHS_TEST);
         assert_eq!(compare_depths(&data), 7);
-
-        data = preprocess_data("src/year2021/day1/depths.txt");
-        assert_eq!(compare_depths(&data), 1184);
+        assert_eq!(compare_depths(&preprocess_data(DEPTHS_REAL)), 1184);
     }
 
     #[test]
     fn part2() {
-        let mut data = preprocess_data("src/year2021/day1/depths-ex.txt");
+        let data = preprocess_data(DEPTHS_TEST);
         assert_eq!(compare_windows(&data), 5);
+        assert_eq!(compare_windows(&preprocess_data(DEPTHS_REAL)), 1158);
+    }
+
+    #[test]
+    fn windows() {
+        let data = preprocess_data(DEPTHS_TEST);
+
+        assert_eq!(
+            window_changes(&data, 1, Comparison::Increase),
+            vec![1, 2, 3, 5, 6, 7, 9]
+        );
+        assert_eq!(window_changes(&data, 1, Comparison::Decrease), vec![4, 8]);
+        assert_eq!(window_changes(&data, 3, Comparison::Unchanged), vec![2]);
+        assert_eq!(window_changes(&data, 3, Comparison::Decrease), vec![3]);
+
+        // Not enough readings for two windows shouldn't panic.
+        assert!(window_changes(&data[..3], 3, Comparison::Increase).is_empty());
+        assert!(window_changes(&[], 3, Comparison::Increase).is_empty());
+        assert!(window_changes(&data, 0, Comparison::Increase).is_empty());
+    }
+
+    // Feeds every depth through a tracker without collecting them first.
+    fn stream_test(contents: &str, window_size: usize) -> usize {
+        let mut tracker = WindowTracker::new(window_size, Comparison::Increase);
+
+        for depth in stream_depths(io::Cursor::new(contents)) {
+            tracker.push(depth.unwrap());
+        }
+
+        tracker.changes
+    }
+
+    #[test]
+    fn streaming() {
+        assert_eq!(stream_test(DEPTHS_TEST, 1), 7);
+        assert_eq!(stream_test(DEPTHS_TEST, 3), 5);
+
+        assert_eq!(stream_test(DEPTHS_REAL, 1), 1184);
+        assert_eq!(stream_test(DEPTHS_REAL, 3), 1158);
 
-        data = preprocess_data("src/year2021/day1/depths.txt");
-        assert_eq!(compare_windows(&data), 1158);
+        // Invalid readings are surfaced as errors rather than panics.
+        let mut depths = stream_depths(io::Cursor::new("199\n\nabc\n"));
+        assert_eq!(depths.next().unwrap().unwrap(), 199);
+        assert!(depths.next().unwrap().is_err());
     }
 }
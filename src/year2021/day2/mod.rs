@@ -1,21 +1,38 @@
-use std::fs;
+use crate::{input, parse, solutions::Answers};
 
-fn preprocess_data(path: &str) -> Vec<String> {
-    fs::read_to_string(path)
-        .expect("Unable to read file")
+// Holds the contents of each of the input files.
+const INSTRUCTIONS_TEST: &str = include_str!("instructions-ex.txt");
+const INSTRUCTIONS_REAL: &str = include_str!("instructions.txt");
+
+fn preprocess_data(contents: &str) -> Vec<String> {
+    input::normalise(contents)
         .lines()
         .map(String::from)
         .collect()
 }
 
+pub fn solve(input: &str) -> Answers {
+    let instructions = preprocess_data(input);
+
+    [
+        horiz_times_depth(&instructions),
+        horiz_times_depth_with_aim(&instructions),
+    ]
+    .map(|answer| Some(answer.to_string()))
+}
+
+fn parse_instruction(instruction: &str) -> (&str, i32) {
+    parse::pair(instruction, " ", Ok, parse::integer)
+        .unwrap_or_else(|err| panic!("Invalid instruction '{instruction}': {err}"))
+}
+
 fn horiz_times_depth(instructions: &[String]) -> i32 {
     let mut horizontal = 0;
     let mut depth = 0;
 
     for instruction in instructions {
         // Splits each of the instructions into the direction and amount parts
-        let (direction, amount_str) = instruction.split_once(' ').unwrap();
-        let amount: i32 = amount_str.parse().unwrap();
+        let (direction, amount) = parse_instruction(instruction);
 
         // Each direction has a unique operation results
         match direction {
@@ -36,8 +53,7 @@ fn horiz_times_depth_with_aim(instructions: &[String]) -> i32 {
 
     for instruction in instructions {
         // Splits each of the instructions into the direction and amount parts
-        let (direction, amount_str) = instruction.split_once(' ').unwrap();
-        let amount: i32 = amount_str.parse().unwrap();
+        let (direction, amount) = parse_instruction(instruction);
 
         // Each direction has a unique operation results
         match direction {
@@ -60,19 +76,22 @@ mod tests {
 
     #[test]
     fn part1() {
-        let mut data = preprocess_data("src/year2021/day2/instructions-ex.txt");
+        let data = preprocess_data(INSTRUCTIONS_TEST);
         assert_eq!(horiz_times_depth(&data), 150);
-
-        data = preprocess_data("src/year2021/day2/instructions.txt");
-        assert_eq!(horiz_times_depth(&data), 1488669);
+        assert_eq!(
+            horiz_times_depth(&preprocess_data(INSTRUCTIONS_REAL)),
+            1_488_669
+        );
     }
 
     #[test]
     fn part2() {
-        let mut data = preprocess_data("src/year2021/day2/instructions-ex.txt");
+        let data = preprocess_data(INSTRUCTIONS_TEST);
         assert_eq!(horiz_times_depth_with_aim(&data), 900);
 
-        data = preprocess_data("src/year2021/day2/instructions.txt");
-        assert_eq!(horiz_times_depth_with_aim(&data), 1176514794);
+        assert_eq!(
+            horiz_times_depth_with_aim(&preprocess_data(INSTRUCTIONS_REAL)),
+            1_176_514_794
+        );
     }
 }
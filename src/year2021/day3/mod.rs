@@ -1,107 +1,188 @@
 mod systems;
 
-use std::fs;
-
-fn preprocess_data(path: &str) -> Vec<String> {
-    fs::read_to_string(path)
-        .expect("Unable to read file")
-        .lines()
-        .map(String::from)
-        .collect()
+use crate::{input, solutions::Answers};
+use systems::Criteria;
+
+// Holds the contents of each of the input files.
+const BITS_TEST: &str = include_str!("bits-ex.txt");
+const BITS_REAL: &str = include_str!("bits.txt");
+
+// Holds every bitstring of the report packed into an integer, along with the amount of
+// columns each of them has (leading zeros would otherwise be lost).
+struct Report {
+    width: usize,
+    bitstrings: Vec<u64>,
 }
 
-// Finds the most and least common bits in a column of a list of bistrings,
-// returns the most common bit as the first element, and the least common as the second.
-fn common_in_col(bitstrings: &[String], col: usize) -> [char; 2] {
-    // Counts the number of ones.
-    let mut one_count = 0;
-    // Converting the length of a bitstring to u32 to compare with one_count
-    let bitstring_len: u32 = bitstrings.len().try_into().unwrap();
-
-    // Iterates through the rows of the bitstrings.
-    for bitstring in bitstrings {
-        one_count += bitstring.chars().nth(col).unwrap().to_digit(2).unwrap();
+impl Report {
+    // Parses each line of the report once into a u64 of the same width.
+    fn parse(contents: &str) -> Self {
+        let contents = input::normalise(contents);
+        let mut lines = contents.lines().filter(|line| !line.is_empty()).peekable();
+
+        // Every line should share the width of the first one.
+        let width = lines.peek().map_or(0, |line| line.len());
+        assert!(
+            (1..=64).contains(&width),
+            "Bitstrings must be between 1 and 64 bits wide."
+        );
+
+        let bitstrings = lines
+            .map(|line| {
+                assert_eq!(line.len(), width, "All bitstrings must be the same width.");
+                u64::from_str_radix(line, 2)
+                    .unwrap_or_else(|err| panic!("Invalid bitstring '{line}': {err}"))
+            })
+            .collect();
+
+        Self { width, bitstrings }
     }
 
-    // 1 is the most (or equally) common if it appears in at least half the rows of the current column.
-    // Ceiling division is perfomed to avoid errors while the length is odd.
-    if one_count >= (bitstring_len + 2 - 1) / 2 {
-        ['1', '0']
-    } else {
-        ['0', '1']
+    // Gets the mask that selects a column, where column 0 is the leftmost bit.
+    const fn col_mask(&self, col: usize) -> u64 {
+        1 << (self.width - col - 1)
+    }
+
+    // Gets the mask that selects every column of the report.
+    const fn full_mask(&self) -> u64 {
+        u64::MAX >> (64 - self.width)
     }
 }
 
-fn power_consumption(bitstrings: &[String]) -> i32 {
-    // Most common bit for all bitstrings.
-    let mut gamma = String::new();
+pub fn solve(input: &str) -> Answers {
+    let report = Report::parse(input);
+    let life_support = life_support(&report)
+        .unwrap_or_else(|err| panic!("Could not rate the life support: {err}"));
+
+    [
+        Some(power_consumption(&report).to_string()),
+        Some(life_support.to_string()),
+    ]
+}
 
-    // Least common bit for all bitstrings
-    let mut epsilon = String::new();
+// Counts the number of ones in a column (selected by a mask) of a list of bitstrings.
+fn ones_in_col(bitstrings: &[u64], mask: u64) -> usize {
+    bitstrings
+        .iter()
+        .filter(|&&bitstring| bitstring & mask != 0)
+        .count()
+}
+
+fn power_consumption(report: &Report) -> u128 {
+    // Most common bit for all bitstrings.
+    let mut gamma = 0;
 
     // Iterates through the columns of the bitstrings.
-    for i in 0..bitstrings[0].len() {
-        let commonalities = common_in_col(bitstrings, i);
-        gamma.push(commonalities[0]);
-        epsilon.push(commonalities[1]);
+    for col in 0..report.width {
+        let mask = report.col_mask(col);
+
+        // 1 is the most (or equally) common if it appears in at least half the rows of the column.
+        if ones_in_col(&report.bitstrings, mask) * 2 >= report.bitstrings.len() {
+            gamma |= mask;
+        }
     }
 
-    let gamma_decimal = i32::from_str_radix(&gamma, 2).unwrap();
-    let epsilon_decimal = i32::from_str_radix(&epsilon, 2).unwrap();
+    // Least common bit for all bitstrings is just the inverse of the most common.
+    let epsilon = !gamma & report.full_mask();
 
-    gamma_decimal * epsilon_decimal
+    u128::from(gamma) * u128::from(epsilon)
 }
 
-// Gets the rating for a certain specified system in a recursive fashion.
-fn system_rating(bitstrings: &[String], col: usize, system_type: systems::SystemType) -> i32 {
-    // Base case: If there's only one bitstring left or we've reached the end of the columns,
-    // return the first value in the list.
-    if bitstrings.len() == 1 || col == bitstrings[0].len() {
-        i32::from_str_radix(bitstrings[0].as_str(), 2).unwrap()
-    } else {
+// Gets the rating for a system by narrowing down the bitstrings column by column
+// according to the given criteria. Fails if the bitstrings never narrow down to one.
+fn system_rating(report: &Report, criteria: &Criteria) -> Result<u64, String> {
+    // A single working copy is filtered in place for every column.
+    let mut candidates = report.bitstrings.clone();
+
+    for col in 0..report.width {
+        // Stop early if there's only one bitstring left.
+        if candidates.len() <= 1 {
+            break;
+        }
+
+        let mask = report.col_mask(col);
+
         // Aqcuires the digit we want for the column we want, for the system we want.
-        let wanted_digit = common_in_col(bitstrings, col)[systems::system_idx(&system_type)];
-
-        // Filters the bitstring list to only include digits in the same column that
-        // match the most/least common digit
-        let filtered: Vec<String> = bitstrings
-            .iter()
-            .filter(|bitstring| bitstring.chars().nth(col).unwrap() == wanted_digit)
-            .cloned()
-            .collect();
+        let wanted_digit = criteria.wanted_bit(ones_in_col(&candidates, mask), candidates.len())?;
+
+        // Only keeps bitstrings whose digit in the column matches the wanted digit.
+        candidates.retain(|&bitstring| (bitstring & mask != 0) == wanted_digit);
+    }
 
-        // Recursive call of this function, this time for the next column
-        system_rating(&filtered, col + 1, system_type)
+    match candidates[..] {
+        [rating] => Ok(rating),
+        _ => Err(format!(
+            "Filtering left {} candidates instead of exactly one.",
+            candidates.len()
+        )),
     }
 }
 
+// Multiplies the oxygen and CO2 ratings together.
+fn life_support(report: &Report) -> Result<u64, String> {
+    Ok(system_rating(report, &Criteria::oxygen())? * system_rating(report, &Criteria::co2())?)
+}
+
 #[cfg(test)]
 mod tests {
+    use super::systems::{SystemType, TieBreak};
     use super::*;
 
     #[test]
     fn part1() {
-        let mut data = preprocess_data("src/year2021/day3/bits-ex.txt");
+        let data = Report::parse(BITS_TEST);
         assert_eq!(power_consumption(&data), 198);
-
-        data = preprocess_data("src/year2021/day3/bits.txt");
-        assert_eq!(power_consumption(&data), 3912944);
+        assert_eq!(power_consumption(&Report::parse(BITS_REAL)), 3_912_944);
     }
 
     #[test]
     fn part2() {
-        let mut data = preprocess_data("src/year2021/day3/bits-ex.txt");
-        assert_eq!(
-            system_rating(&data, 0, systems::SystemType::Oxygen)
-                * system_rating(&data, 0, systems::SystemType::CO2),
-            230
+        let data = Report::parse(BITS_TEST);
+        assert_eq!(life_support(&data), Ok(230));
+        assert_eq!(life_support(&Report::parse(BITS_REAL)), Ok(4_996_233));
+    }
+
+    #[test]
+    fn wide_bitstrings() {
+        // Three 64-bit wide lines, where only the second column is mostly zeros.
+        let contents = format!(
+            "10{}\n10{}\n01{}\n",
+            "0".repeat(62),
+            "1".repeat(62),
+            "1".repeat(62)
         );
+        let data = Report::parse(&contents);
+        let second_col = 1 << 62;
 
-        data = preprocess_data("src/year2021/day3/bits.txt");
+        assert_eq!(data.width, 64);
+        assert_eq!(
+            power_consumption(&data),
+            u128::from(u64::MAX ^ second_col) * u128::from(second_col)
+        );
+        assert_eq!(
+            system_rating(&data, &Criteria::oxygen()).unwrap(),
+            u64::MAX ^ second_col
+        );
         assert_eq!(
-            system_rating(&data, 0, systems::SystemType::Oxygen)
-                * system_rating(&data, 0, systems::SystemType::CO2),
-            4996233
+            system_rating(&data, &Criteria::co2()).unwrap(),
+            u64::MAX >> 1
         );
     }
+
+    #[test]
+    fn criteria() {
+        let data = Report::parse(BITS_TEST);
+
+        // Flipping the tie break changes which bitstring oxygen ends up on.
+        let oxygen_zero = Criteria::new(SystemType::Oxygen, TieBreak::PreferZero);
+        assert_eq!(system_rating(&data, &oxygen_zero), Ok(0b10110));
+
+        // The second column of the example is tied, which is an error when asked to be.
+        let strict = Criteria::new(SystemType::Oxygen, TieBreak::Error);
+        assert!(system_rating(&data, &strict).is_err());
+
+        // Duplicate bitstrings can never be narrowed down to a single one.
+        let duplicates = Report::parse("101\n101\n");
+        assert!(system_rating(&duplicates, &Criteria::oxygen()).is_err());
+    }
 }
@@ -1,11 +1,60 @@
+#[derive(Clone, Copy)]
 pub enum SystemType {
     Oxygen,
     CO2,
 }
 
-pub fn system_idx(system_type: &SystemType) -> usize {
-    match system_type {
-        SystemType::Oxygen => 0,
-        SystemType::CO2 => 1,
+// Decides which bit is kept when a column has as many ones as zeros.
+#[derive(Clone, Copy)]
+pub enum TieBreak {
+    PreferOne,
+    PreferZero,
+    Error,
+}
+
+// The bit criteria used to filter down bitstrings when looking for a system's rating.
+pub struct Criteria {
+    system_type: SystemType,
+    tie_break: TieBreak,
+}
+
+impl Criteria {
+    pub const fn new(system_type: SystemType, tie_break: TieBreak) -> Self {
+        Self {
+            system_type,
+            tie_break,
+        }
+    }
+
+    // Oxygen keeps the most common bit, and 1 on ties.
+    pub const fn oxygen() -> Self {
+        Self::new(SystemType::Oxygen, TieBreak::PreferOne)
+    }
+
+    // CO2 keeps the least common bit, and 0 on ties.
+    pub const fn co2() -> Self {
+        Self::new(SystemType::CO2, TieBreak::PreferZero)
+    }
+
+    // Determines the bit to keep for a column, given how many of its rows are ones.
+    pub fn wanted_bit(&self, one_count: usize, total: usize) -> Result<bool, String> {
+        let zero_count = total - one_count;
+
+        if one_count == zero_count {
+            return match self.tie_break {
+                TieBreak::PreferOne => Ok(true),
+                TieBreak::PreferZero => Ok(false),
+                TieBreak::Error => Err(format!(
+                    "Column is tied with {one_count} ones and {zero_count} zeros."
+                )),
+            };
+        }
+
+        let one_most_common = one_count > zero_count;
+
+        Ok(match self.system_type {
+            SystemType::Oxygen => one_most_common,
+            SystemType::CO2 => !one_most_common,
+        })
     }
 }
@@ -1,8 +1,12 @@
-use std::fs;
+use crate::{grid::Grid, input, solutions::Answers};
 
-// The tables will be a 2D vector holding optional unsigned integers.
+// Holds the contents of each of the input files.
+const BINGO_TEST: &str = include_str!("bingo-ex.txt");
+const BINGO_REAL: &str = include_str!("bingo.txt");
+
+// The tables will be a grid holding optional unsigned integers.
 // They're optional since they'll be set to None if they get marked.
-type Table = Vec<Vec<Option<u32>>>;
+type Table = Grid<Option<u32>>;
 
 #[derive(PartialEq)]
 enum Order {
@@ -17,43 +21,41 @@ struct BingoGroup {
 }
 
 impl BingoGroup {
-    // Reads data from file into the table group.
-    fn new(path: &str) -> BingoGroup {
-        let binding = fs::read_to_string(path).unwrap();
+    // Reads the contents of the input into the table group.
+    fn new(contents: &str) -> Self {
+        let binding = input::normalise(contents);
 
-        let mut data = binding
-            .lines() // Splitting file into a vector of lines.
-            .map(String::from); // Converting the lines from &str to String
+        let mut data = input::paragraphs(&binding);
 
         let moves = data
-            .next() // Moves are defined in the first line.
-            .unwrap()
+            .next() // Moves are defined in the first paragraph.
+            .unwrap_or_else(|| panic!("Bingo input has no moves."))
             .split(',') // The moves are comma seperated.
-            .map(|num| num.parse().unwrap()) // Converting each number to u32.
+            .map(|num| {
+                // Converting each number to u32.
+                num.parse()
+                    .unwrap_or_else(|err| panic!("Invalid move '{num}': {err}"))
+            })
             .collect();
 
-        let mut table_size = 0;
-
         let tables: Vec<Table> = data
-            .filter(|line| !line.is_empty()) // Remove all empty lines.
-            .map(|line| {
-                // For each line, split into a vector and convert each number to u32.
-                let split_row: Vec<Option<u32>> = line
-                    .split_whitespace()
-                    .map(|num| Some(num.parse().unwrap()))
-                    .collect();
-
-                // Set the table size to the length of the row since we know it'll be square.
-                table_size = split_row.len();
-
-                split_row
+            .map(|table| {
+                // For each row of the table, split it up and convert each number to u32.
+                let rows = table.lines().map(|line| {
+                    line.split_whitespace()
+                        .map(|num| {
+                            Some(num.parse().unwrap_or_else(|err| {
+                                panic!("Invalid table number '{num}': {err}")
+                            }))
+                        })
+                        .collect()
+                });
+
+                Grid::from_rows(rows).unwrap_or_else(|err| panic!("Invalid bingo table: {err}"))
             })
-            .collect::<Table>() // Collects into one big table.
-            .chunks(table_size) // Splits big table into many smaller tables of a constant size.
-            .map(<[Vec<Option<u32>>]>::to_vec) // Converts each chunk into a vector.
             .collect();
 
-        BingoGroup {
+        Self {
             bingo_idxs: Vec::with_capacity(tables.len()),
             moves,
             tables,
@@ -62,12 +64,12 @@ impl BingoGroup {
 
     fn mark_tables(&mut self, move_idx: usize) {
         // Flattening the tables to iterate through each value easily.
-        let flattened_tables = self.tables.iter_mut().flatten().flatten();
+        let flattened_tables = self.tables.iter_mut().flat_map(Grid::iter_mut);
 
         // Values will be marked if it matches with the current move passed in.
         for num in flattened_tables {
-            // Only consider values not already marked (is_some)
-            if num.is_some() && num.unwrap() == self.moves[move_idx] {
+            // Marked values are None, so they can never match the move again.
+            if *num == Some(self.moves[move_idx]) {
                 *num = None;
             }
         }
@@ -77,30 +79,25 @@ impl BingoGroup {
     fn check_bingo(&mut self, order: &Order) -> Option<usize> {
         // Enumerating through each of the tables.
         for (i, table) in self.tables.iter().enumerate() {
-            // Enumerating through each of the rows of the table.
-            for (j, row) in table.iter().enumerate() {
-                // Row and column iterators.
-                let mut row = row.iter();
-                let mut col = table.iter().map(|row| &row[j]);
-
-                // Checks if the current row or column are all marked
-                if row.all(Option::is_none) || col.all(Option::is_none) {
-                    // Returns the index immediately if we want the first winning table.
-                    if order == &Order::First {
-                        return Some(i);
+            // Checks if any row or column of the table is all marked.
+            if table.rows().any(|row| row.iter().all(Option::is_none))
+                || table.columns().any(|mut col| col.all(Option::is_none))
+            {
+                // Returns the index immediately if we want the first winning table.
+                if order == &Order::First {
+                    return Some(i);
+                }
+
+                // Or if we want the last winning table...
+                if order == &Order::Last {
+                    // Add the current table index the the winning table list if it isn't in.
+                    if !self.bingo_idxs.contains(&i) {
+                        self.bingo_idxs.push(i);
                     }
 
-                    // Or if we want the last winning table...
-                    if order == &Order::Last {
-                        // Add the current table index the the winning table list if it isn't in.
-                        if !self.bingo_idxs.contains(&i) {
-                            self.bingo_idxs.push(i);
-                        }
-
-                        // Then return the table index if every table has now won.
-                        if self.bingo_idxs.len() == self.tables.len() {
-                            return Some(i);
-                        }
+                    // Then return the table index if every table has now won.
+                    if self.bingo_idxs.len() == self.tables.len() {
+                        return Some(i);
                     }
                 }
             }
@@ -112,7 +109,7 @@ impl BingoGroup {
 
     fn get_score(&self, move_idx: usize, table_idx: usize) -> u32 {
         // Gets the sum of all unmarked numbers of the winning table.
-        let sum_unmarked: u32 = self.tables[table_idx].iter().flatten().flatten().sum();
+        let sum_unmarked: u32 = self.tables[table_idx].iter().flatten().sum();
 
         // Returns the sum multiplied by the winning move.
         sum_unmarked * self.moves[move_idx]
@@ -137,13 +134,19 @@ fn bingo(bingo_group: &mut BingoGroup, order: &Order) -> Option<u32> {
     None
 }
 
+// Plays the game until the first table wins, then again until the last one does.
+pub fn solve(input: &str) -> Answers {
+    [Order::First, Order::Last]
+        .map(|order| bingo(&mut BingoGroup::new(input), &order).map(|score| score.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     // Simple test utility function.
-    fn bingo_test(path: &str, order: &Order, expected: u32) {
-        let mut bingo_group = BingoGroup::new(path);
+    fn bingo_test(contents: &str, order: &Order, expected: u32) {
+        let mut bingo_group = BingoGroup::new(contents);
         let result = bingo(&mut bingo_group, order);
 
         match result {
@@ -154,13 +157,13 @@ mod tests {
 
     #[test]
     fn part1() {
-        bingo_test("src/year2021/day4/bingo-ex.txt", &Order::First, 4512);
-        bingo_test("src/year2021/day4/bingo.txt", &Order::First, 11536);
+        bingo_test(BINGO_TEST, &Order::First, 4512);
+        bingo_test(BINGO_REAL, &Order::First, 11536);
     }
 
     #[test]
     fn part2() {
-        bingo_test("src/year2021/day4/bingo-ex.txt", &Order::Last, 1924);
-        bingo_test("src/year2021/day4/bingo.txt", &Order::Last, 1284);
+        bingo_test(BINGO_TEST, &Order::Last, 1924);
+        bingo_test(BINGO_REAL, &Order::Last, 1284);
     }
 }
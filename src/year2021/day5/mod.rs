@@ -1,4 +1,55 @@
-fn something() {}
+use crate::{
+    geometry::{Point, PointCounter, Segment},
+    input, parse,
+    solutions::Answers,
+};
+
+// Holds the contents of each of the input files.
+const VENTS_TEST: &str = include_str!("vents-ex.txt");
+const VENTS_REAL: &str = include_str!("vents.txt");
+
+pub fn main() {
+    println!("{}", overlapping_points(VENTS_TEST, false));
+    println!("{}", overlapping_points(VENTS_REAL, false));
+    println!("{}", overlapping_points(VENTS_TEST, true));
+    println!("{}", overlapping_points(VENTS_REAL, true));
+
+    // The example is small enough to look at.
+    println!("{}", vent_diagram(VENTS_TEST, true));
+}
+
+pub fn solve(input: &str) -> Answers {
+    [false, true].map(|diagonals| Some(overlapping_points(input, diagonals).to_string()))
+}
+
+// Reads every line of vents, written like '0,9 -> 5,9', into a segment.
+fn parse_vents(file_contents: &str) -> Vec<Segment> {
+    let file_contents = input::normalise(file_contents);
+
+    parse::lines(&file_contents, |line| {
+        let [x1, y1, x2, y2] = parse::integers(line, "{},{} -> {},{}")?;
+        Ok(Segment::new(Point::new(x1, y1), Point::new(x2, y2)))
+    })
+    .unwrap_or_else(|err| panic!("Could not read the vents: {err}"))
+}
+
+// Counts how many vents cover each point, only using diagonal vents if asked to.
+fn vent_diagram(file_contents: &str, diagonals: bool) -> PointCounter {
+    let mut counter = PointCounter::new();
+
+    for vent in parse_vents(file_contents) {
+        if vent.is_axis_aligned() || (diagonals && vent.is_diagonal()) {
+            counter.add_segment(&vent);
+        }
+    }
+
+    counter
+}
+
+// Counts the points where at least two vents overlap.
+fn overlapping_points(file_contents: &str, diagonals: bool) -> usize {
+    vent_diagram(file_contents, diagonals).at_least(2)
+}
 
 #[cfg(test)]
 mod tests {
@@ -6,11 +57,30 @@ mod tests {
 
     #[test]
     fn part1() {
-        something();
+        assert_eq!(overlapping_points(VENTS_TEST, false), 5);
+        assert_eq!(overlapping_points(VENTS_REAL, false), 5147);
     }
 
     #[test]
     fn part2() {
-        something();
+        assert_eq!(overlapping_points(VENTS_TEST, true), 12);
+        assert_eq!(overlapping_points(VENTS_REAL, true), 16925);
+    }
+
+    #[test]
+    fn diagram() {
+        let expected = "\
+1.1....11.
+.111...2..
+..2.1.111.
+...1.2.2..
+.112313211
+...1.2....
+..1...1...
+.1.....1..
+1.......1.
+222111....";
+
+        assert_eq!(vent_diagram(VENTS_TEST, true).to_string(), expected);
     }
 }
@@ -12,3 +12,32 @@ mod day4;
 
 #[allow(dead_code)]
 mod day5;
+
+use crate::solutions::Solver;
+
+pub fn select_day(args: &[String]) {
+    let module = args.get(2).map_or_else(
+        || {
+            println!("No day name specified, defaulting to day1.");
+            "day1"
+        },
+        String::as_str,
+    );
+
+    match module {
+        "day1" => day1::main(args),
+        "day5" => day5::main(),
+        _ => println!("Invalid module name."),
+    }
+}
+
+pub fn solver(day: u32) -> Option<Solver> {
+    match day {
+        1 => Some(day1::solve),
+        2 => Some(day2::solve),
+        3 => Some(day3::solve),
+        4 => Some(day4::solve),
+        5 => Some(day5::solve),
+        _ => None,
+    }
+}
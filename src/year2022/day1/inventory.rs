@@ -0,0 +1,120 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use super::{convert_to_calorie, Calorie};
+use crate::input;
+
+/// Summary statistics over the calorie totals of every elf.
+#[derive(Debug, PartialEq)]
+pub struct Statistics {
+    pub min: Calorie,
+    pub max: Calorie,
+    pub mean: f64,
+    pub median: f64,
+}
+
+/// `Inventory` struct.
+/// * `elves` - The calories of every item carried, grouped by elf in file order.
+pub struct Inventory {
+    elves: Vec<Vec<Calorie>>,
+}
+
+impl Inventory {
+    /// Parses the calorie groups of `file_contents`, one group per elf.
+    pub fn parse(file_contents: &str) -> Self {
+        let file_contents = input::normalise(file_contents);
+
+        Self {
+            elves: input::paragraphs(&file_contents) // Splits into calorie groups.
+                .map(|calorie_group| calorie_group.lines().map(convert_to_calorie).collect())
+                .collect(),
+        }
+    }
+
+    /// Gets the summed calories of each elf.
+    pub fn totals(&self) -> impl Iterator<Item = Calorie> + '_ {
+        self.elves.iter().map(|items| items.iter().sum())
+    }
+
+    /// Gets the amount of items each elf carries.
+    pub fn item_counts(&self) -> Vec<usize> {
+        self.elves.iter().map(Vec::len).collect()
+    }
+
+    /// Gets the indices and totals of the `amount` elves carrying the most calories,
+    /// from most to least. Keeps a min-heap of at most `amount` elves, so runs in O(n log k).
+    pub fn top(&self, amount: usize) -> Vec<(usize, Calorie)> {
+        let mut heap = BinaryHeap::with_capacity(amount + 1);
+
+        for (idx, total) in self.totals().enumerate() {
+            // Earlier elves win ties, so the index is reversed along with the total.
+            heap.push(Reverse((total, Reverse(idx))));
+
+            // Drops the smallest elf once there are too many.
+            if heap.len() > amount {
+                heap.pop();
+            }
+        }
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((total, Reverse(idx)))| (idx, total))
+            .collect()
+    }
+
+    /// Computes the min, max, mean and median of the elf totals, if there are any elves.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn statistics(&self) -> Option<Statistics> {
+        let mut totals = self.totals().collect::<Vec<Calorie>>();
+        totals.sort_unstable();
+
+        let (&lowest, &highest) = (totals.first()?, totals.last()?);
+        let mean = totals.iter().map(|&total| f64::from(total)).sum::<f64>() / totals.len() as f64;
+
+        // The median is the average of the two middle values when the count is even.
+        let half = totals.len() / 2;
+        let median = if totals.len() % 2 == 0 {
+            let (low, high) = (f64::from(totals[half - 1]), f64::from(totals[half]));
+            low + (high - low) / 2.0
+        } else {
+            f64::from(totals[half])
+        };
+
+        Some(Statistics {
+            min: lowest,
+            max: highest,
+            mean,
+            median,
+        })
+    }
+
+    /// Builds a printable report of the statistics and the `amount` top elves.
+    pub fn report(&self, amount: usize) -> String {
+        let item_counts = self.item_counts();
+        let mut lines = vec![
+            format!("Elves: {}", self.elves.len()),
+            format!("Items: {}", item_counts.iter().sum::<usize>()),
+        ];
+
+        if let Some(stats) = self.statistics() {
+            lines.extend([
+                format!("Min: {}", stats.min),
+                format!("Max: {}", stats.max),
+                format!("Mean: {:.2}", stats.mean),
+                format!("Median: {:.1}", stats.median),
+            ]);
+        }
+
+        lines.push(format!("Top {amount}:"));
+
+        // Elves are numbered from 1 to match their position in the file.
+        lines.extend(self.top(amount).into_iter().map(|(idx, total)| {
+            format!(
+                "  Elf {}: {total} calories over {} items",
+                idx + 1,
+                item_counts[idx]
+            )
+        }));
+
+        lines.join("\n") + "\n"
+    }
+}
@@ -1,34 +1,46 @@
+mod inventory;
+
+use crate::{input, solutions::Answers};
+use inventory::Inventory;
+
 const CALORIES_TEST: &str = include_str!("calories-ex.txt");
 const CALORIES_REAL: &str = include_str!("calories.txt");
 
 type Calorie = u32;
 
-pub fn main() {
+pub fn main(args: &[String]) {
+    // Prints inventory statistics for any calorie file when asked for a report.
+    if let (Some("report"), Some(path)) = (args.get(3).map(String::as_str), args.get(4)) {
+        let amount = args.get(5).map_or(3, |amount| {
+            amount
+                .parse()
+                .unwrap_or_else(|err| panic!("Could not parse amount '{amount}': {err}"))
+        });
+
+        print!("{}", Inventory::parse(&input::read(path)).report(amount));
+        return;
+    }
+
     println!("{}", most_calories(CALORIES_TEST, 1));
     println!("{}", most_calories(CALORIES_REAL, 3));
 }
 
+pub fn solve(input: &str) -> Answers {
+    [1, 3].map(|amount| Some(most_calories(input, amount).to_string()))
+}
+
 // Attempts to convert a string to a calorie.
 fn convert_to_calorie(line: &str) -> Calorie {
-    line.parse::<Calorie>().map_or_else(
-        |err| panic!("Could not convert line to calorie: {err}"),
-        |calorie| calorie,
-    )
+    line.parse::<Calorie>()
+        .unwrap_or_else(|err| panic!("Could not convert line to calorie: {err}"))
 }
 
 fn most_calories(file_contents: &str, amount: usize) -> Calorie {
-    let mut elf_calories = file_contents
-        .split("\n\n") // Splits into calorie groups.
-        .map(|calorie_group| {
-            calorie_group
-                .lines() // Splits calorie groups into lines.
-                .map(convert_to_calorie) // Attempt convert each line to calorie.
-                .sum::<Calorie>() // Sums the group of calories.
-        })
-        .collect::<Vec<Calorie>>(); // Collects all summed calories into a Vector of elves.
-
-    elf_calories.sort_unstable(); // Sorts the vector.
-    elf_calories.into_iter().rev().take(amount).sum() // Returns sum of largest (n) elves.
+    Inventory::parse(file_contents)
+        .top(amount)
+        .into_iter()
+        .map(|(_, total)| total)
+        .sum() // Returns sum of largest (n) elves.
 }
 
 #[cfg(test)]
@@ -46,4 +58,40 @@ mod tests {
         assert_eq!(45_000, most_calories(CALORIES_TEST, 3));
         assert_eq!(201_491, most_calories(CALORIES_REAL, 3));
     }
+
+    #[test]
+    fn inventory() {
+        let inventory = Inventory::parse(CALORIES_TEST);
+
+        assert_eq!(
+            vec![(3, 24_000), (2, 11_000), (4, 10_000)],
+            inventory.top(3)
+        );
+        assert_eq!(vec![3, 1, 2, 3, 1], inventory.item_counts());
+        assert_eq!(
+            Some(inventory::Statistics {
+                min: 4_000,
+                max: 24_000,
+                mean: 11_000.0,
+                median: 10_000.0,
+            }),
+            inventory.statistics()
+        );
+
+        // Asking for more elves than there are just returns all of them.
+        assert_eq!(5, inventory.top(10).len());
+        assert!(inventory.top(0).is_empty());
+    }
+
+    #[test]
+    fn messy_input() {
+        // Windows line endings, extra blank lines and trailing whitespace shouldn't matter.
+        let messy = CALORIES_TEST
+            .replace("\n\n", " \n\n\n")
+            .replace('\n', "\r\n")
+            + "\r\n\r\n";
+
+        assert_eq!(24_000, most_calories(&messy, 1));
+        assert_eq!(45_000, most_calories(&messy, 3));
+    }
 }
@@ -0,0 +1,86 @@
+use super::{game::Outcome, Score};
+
+/// The details of a single round of a strategy.
+/// * `opponent` - Symbol of the opponent's move.
+/// * `player` - Symbol of the player's move, named with the opponent's symbols.
+/// * `outcome` - The result of the round for the player.
+/// * `shape_points` - Points for the move the player made.
+/// * `outcome_points` - Points for the outcome of the round.
+pub struct Round {
+    pub opponent: char,
+    pub player: char,
+    pub outcome: Outcome,
+    pub shape_points: Score,
+    pub outcome_points: Score,
+}
+
+impl Round {
+    /// Total points the player got this round.
+    pub const fn score(&self) -> Score {
+        self.shape_points + self.outcome_points
+    }
+}
+
+/// How a breakdown of rounds is rendered.
+#[derive(Clone, Copy)]
+pub enum Format {
+    /// Aligned columns with a total at the bottom, meant for reading.
+    Table,
+    /// Comma separated values with a header, meant for other tools.
+    Csv,
+}
+
+impl Format {
+    /// Renders the rounds, numbering them by their line in the strategy.
+    pub fn render(self, rounds: &[Round]) -> String {
+        match self {
+            Self::Table => Self::render_table(rounds),
+            Self::Csv => Self::render_csv(rounds),
+        }
+    }
+
+    fn render_table(rounds: &[Round]) -> String {
+        let mut lines = vec![format!(
+            "{:>5} {:>8} {:>6} {:>7} {:>5} {:>7} {:>5}",
+            "Line", "Opponent", "Player", "Outcome", "Shape", "Outcome", "Score"
+        )];
+
+        lines.extend(rounds.iter().enumerate().map(|(line, round)| {
+            format!(
+                "{:>5} {:>8} {:>6} {:>7} {:>5} {:>7} {:>5}",
+                line + 1,
+                round.opponent,
+                round.player,
+                format!("{:?}", round.outcome),
+                round.shape_points,
+                round.outcome_points,
+                round.score()
+            )
+        }));
+
+        let total = rounds.iter().map(Round::score).sum::<Score>();
+        lines.push(format!("{:>5} {total:>43}", "Total"));
+
+        lines.join("\n") + "\n"
+    }
+
+    fn render_csv(rounds: &[Round]) -> String {
+        let mut lines =
+            vec!["line,opponent,player,outcome,shape_points,outcome_points,score".to_string()];
+
+        lines.extend(rounds.iter().enumerate().map(|(line, round)| {
+            format!(
+                "{},{},{},{:?},{},{},{}",
+                line + 1,
+                round.opponent,
+                round.player,
+                round.outcome,
+                round.shape_points,
+                round.outcome_points,
+                round.score()
+            )
+        }));
+
+        lines.join("\n") + "\n"
+    }
+}
@@ -0,0 +1,195 @@
+use super::Score;
+
+/// The result of a round from the player's point of view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    /// Index of the outcome in a `[loss, draw, win]` table.
+    pub const fn idx(self) -> usize {
+        match self {
+            Self::Loss => 0,
+            Self::Draw => 1,
+            Self::Win => 2,
+        }
+    }
+
+    /// All outcomes in table order.
+    pub const ALL: [Self; 3] = [Self::Loss, Self::Draw, Self::Win];
+}
+
+/// How the player's column of the strategy should be read.
+#[derive(Clone, Copy)]
+pub enum Interpretation {
+    /// Each symbol is the move to play.
+    Moves,
+    /// Each symbol is the outcome to aim for (loss, draw, win).
+    Outcomes,
+}
+
+/// `CyclicGame` struct, a game where every move beats the half of the moves before it in the cycle.
+/// * `shape_scores` - Points for playing each move, the length is the amount of moves.
+/// * `outcome_scores` - Points for losing, drawing and winning.
+/// * `opponent_symbols` - The symbol used by the opponent for each move.
+/// * `player_symbols` - The symbol used by the player for each move or outcome.
+/// * `interpretation` - Whether `player_symbols` are moves or outcomes.
+// Named for the kind of game it plays, which `Cyclic` alone wouldn't get across.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone)]
+pub struct CyclicGame {
+    shape_scores: Vec<Score>,
+    outcome_scores: [Score; 3],
+    opponent_symbols: Vec<char>,
+    player_symbols: Vec<char>,
+    interpretation: Interpretation,
+}
+
+impl CyclicGame {
+    /// Creates a new `CyclicGame`, panicking if the configuration is inconsistent.
+    pub fn new(
+        shape_scores: Vec<Score>,
+        outcome_scores: [Score; 3],
+        opponent_symbols: &str,
+        player_symbols: &str,
+        interpretation: Interpretation,
+    ) -> Self {
+        let moves = shape_scores.len();
+
+        // With an even amount of moves, some pairs of moves would neither win nor lose.
+        assert!(
+            moves % 2 == 1,
+            "A cyclic game needs an odd amount of moves, got {moves}."
+        );
+
+        let opponent_symbols = opponent_symbols.chars().collect::<Vec<_>>();
+        let player_symbols = player_symbols.chars().collect::<Vec<_>>();
+
+        assert_eq!(
+            opponent_symbols.len(),
+            moves,
+            "The opponent needs exactly one symbol per move."
+        );
+
+        let expected_player_symbols = match interpretation {
+            Interpretation::Moves => moves,
+            Interpretation::Outcomes => Outcome::ALL.len(),
+        };
+
+        assert_eq!(
+            player_symbols.len(),
+            expected_player_symbols,
+            "The player has the wrong amount of symbols for the interpretation."
+        );
+
+        Self {
+            shape_scores,
+            outcome_scores,
+            opponent_symbols,
+            player_symbols,
+            interpretation,
+        }
+    }
+
+    /// The classic game, with the opponent on `ABC` and the player on `XYZ`.
+    pub fn rock_paper_scissors(interpretation: Interpretation) -> Self {
+        Self::new(vec![1, 2, 3], [0, 3, 6], "ABC", "XYZ", interpretation)
+    }
+
+    /// Copies the game with a different assignment of the player's symbols.
+    pub fn remap_player(&self, player_symbols: &[char]) -> Self {
+        Self::new(
+            self.shape_scores.clone(),
+            self.outcome_scores,
+            &self.opponent_symbols.iter().collect::<String>(),
+            &player_symbols.iter().collect::<String>(),
+            self.interpretation,
+        )
+    }
+
+    /// The player's symbols, in move or outcome order.
+    pub fn player_symbols(&self) -> &[char] {
+        &self.player_symbols
+    }
+
+    /// Describes what each of the player's symbols stands for, naming moves
+    /// by the opponent's symbol for them.
+    pub fn describe_player_mapping(&self) -> String {
+        self.player_symbols
+            .iter()
+            .enumerate()
+            .map(|(idx, symbol)| match self.interpretation {
+                Interpretation::Moves => format!("{symbol}={}", self.opponent_symbols[idx]),
+                Interpretation::Outcomes => format!("{symbol}={:?}", Outcome::ALL[idx]),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Determines the outcome for the player when `player` is played against `opponent`.
+    pub fn determine_winner(&self, opponent: usize, player: usize) -> Outcome {
+        let moves = self.shape_scores.len();
+        let diff = (player + moves - opponent) % moves;
+
+        if diff == 0 {
+            Outcome::Draw
+        } else if diff <= moves / 2 {
+            Outcome::Win
+        } else {
+            Outcome::Loss
+        }
+    }
+
+    /// Picks the move that gets the `outcome` against `opponent`,
+    /// the closest one in the cycle when there are several.
+    pub fn respond(&self, opponent: usize, outcome: Outcome) -> usize {
+        let moves = self.shape_scores.len();
+
+        match outcome {
+            Outcome::Loss => (opponent + moves - 1) % moves,
+            Outcome::Draw => opponent,
+            Outcome::Win => (opponent + 1) % moves,
+        }
+    }
+
+    /// Points for the player for playing a move.
+    pub fn shape_score(&self, player: usize) -> Score {
+        self.shape_scores[player]
+    }
+
+    /// Points for the player for the outcome of the round.
+    pub const fn outcome_score(&self, outcome: Outcome) -> Score {
+        self.outcome_scores[outcome.idx()]
+    }
+
+    /// The symbol naming a move, taken from the opponent's symbols.
+    pub fn move_symbol(&self, mv: usize) -> char {
+        self.opponent_symbols[mv]
+    }
+
+    /// Attempts to map the opponent's symbol to a move.
+    pub fn opponent_move(&self, symbol: char) -> Result<usize, String> {
+        self.opponent_symbols
+            .iter()
+            .position(|&known| known == symbol)
+            .ok_or_else(|| format!("Unknown opponent symbol '{symbol}'."))
+    }
+
+    /// Attempts to map the player's symbol to a move, resolving it against
+    /// the opponent's move if the symbols are outcomes.
+    pub fn player_move(&self, symbol: char, opponent: usize) -> Result<usize, String> {
+        let idx = self
+            .player_symbols
+            .iter()
+            .position(|&known| known == symbol)
+            .ok_or_else(|| format!("Unknown player symbol '{symbol}'."))?;
+
+        Ok(match self.interpretation {
+            Interpretation::Moves => idx,
+            Interpretation::Outcomes => self.respond(opponent, Outcome::ALL[idx]),
+        })
+    }
+}
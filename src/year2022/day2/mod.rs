@@ -1,76 +1,191 @@
+mod breakdown;
+mod game;
+
+use crate::{input, solutions::Answers};
+use breakdown::{Format, Round};
+use game::{CyclicGame, Interpretation};
+
 const STRATEGY_TEST: &str = include_str!("strategy-ex.txt");
 const STRATEGY_REAL: &str = include_str!("strategy.txt");
 
-const ASCII_NORMAL: u8 = b"A"[0];
-const PLAYER_ASCII_DIFF: u8 = b"X"[0] - b"A"[0];
-
 type Score = u32;
 
 fn rock_paper_scissors(file_contents: &str, decrypt: bool) -> Score {
-    // Takes each line of the strategy, computes the score, and takes the sum.
-    file_contents
+    // Decrypting reads the player's column as outcomes rather than moves.
+    let interpretation = if decrypt {
+        Interpretation::Outcomes
+    } else {
+        Interpretation::Moves
+    };
+
+    total_score(
+        &CyclicGame::rock_paper_scissors(interpretation),
+        file_contents,
+    )
+}
+
+// Takes each line of the strategy, computes the score with the given game, and takes the sum.
+fn total_score(game: &CyclicGame, file_contents: &str) -> Score {
+    input::normalise(file_contents)
         .lines()
-        .map(|line| play_round(line, decrypt))
+        .map(|line| play_round(game, line))
         .sum()
 }
 
-// Takes a line and plays a single round of RPS with it.
-fn play_round(play: &str, decrypt: bool) -> Score {
-    // Splits the line into the opponent and player moves.
-    match play.split_whitespace().collect::<Vec<_>>()[..] {
-        [opponent, player] => preprocess_plays(opponent, player),
+// Takes a line and plays a single round of the game with it.
+fn play_round(game: &CyclicGame, play: &str) -> Score {
+    resolve_round(game, play).score()
+}
+
+// Takes a line and works out the moves, outcome and points of the round.
+fn resolve_round(game: &CyclicGame, play: &str) -> Round {
+    // Splits the line into the opponent and player symbols.
+    let (opponent, player) = match play.split_whitespace().collect::<Vec<_>>()[..] {
+        [opponent, player] => preprocess_plays(game, opponent, player),
         _ => panic!("The line must have exactly two characters seperated by whitespace"),
     }
-    .into_iter()
-    // Determines the winner given the two plays.
-    .reduce(|opponent, player| determine_winner(i16::from(opponent), i16::from(player), decrypt))
-    .map_or_else(|| panic!("Empty line encountered"), |score| score)
-    .into()
+    .unwrap_or_else(|err| panic!("Could not read the plays of line '{play}': {err}"));
+
+    // Determines the winner given the two plays, then scores the player.
+    let outcome = game.determine_winner(opponent, player);
+
+    Round {
+        opponent: game.move_symbol(opponent),
+        player: game.move_symbol(player),
+        outcome,
+        shape_points: game.shape_score(player),
+        outcome_points: game.outcome_score(outcome),
+    }
 }
 
-// Takes the individual characters of a play as strings,
-// then preprocesses them to be normalized ascii values.
-fn preprocess_plays(opponent: &str, player: &str) -> [u8; 2] {
-    [
-        // 'A' maps to 0, 'B' maps to 1, 'C' maps to 2
-        (opponent.as_bytes().first().map_or_else(
-            || panic!("Could find the opponent character"),
-            |ascii| ascii,
-        ) - ASCII_NORMAL),
-        // 'X' maps to 0, 'Y' maps to 1, 'Z' maps to 2
-        (player.as_bytes().first().map_or_else(
-            || panic!("Could not find the player character"),
-            |ascii| ascii,
-        ) - (ASCII_NORMAL + PLAYER_ASCII_DIFF)),
-    ]
+// Lists the breakdown of every round of the strategy in the given format.
+fn score_breakdown(game: &CyclicGame, file_contents: &str, format: Format) -> String {
+    let rounds = input::normalise(file_contents)
+        .lines()
+        .map(|line| resolve_round(game, line))
+        .collect::<Vec<_>>();
+
+    format.render(&rounds)
 }
 
-// Takes the opponent and player's character ascii values, determines the winner,
-// and returns the appropriate score.
-// The ascii values are converted to integers, since the solution involves working with
-// negative numbers, and we don't want to overflow.
-fn determine_winner(opponent: i16, player: i16, decrypt: bool) -> u8 {
-    let player_adj = if decrypt {
-        // If decrypting, we adjust to either win, lose or tie depending on what 'player' is.
-        (opponent + (player - 1)).rem_euclid(3)
-    } else {
-        player // If not decrypting, we assume the player corresponds to another move.
+// Reads the strategy at `path`, falling back to the real strategy if there is none.
+fn read_strategy(path: Option<&String>) -> String {
+    input::read_or(path, || STRATEGY_REAL.to_string())
+}
+
+// Takes the individual characters of a play as strings,
+// then maps them to the moves of the game.
+fn preprocess_plays(
+    game: &CyclicGame,
+    opponent: &str,
+    player: &str,
+) -> Result<(usize, usize), String> {
+    let symbol = |play: &str| {
+        play.chars()
+            .next()
+            .ok_or_else(|| "Could not find the character".to_string())
     };
 
-    // Computes the score, by having 3 * the win status, followed by adding the player's move.
-    (3 * (player_adj - opponent + 1).rem_euclid(3) + (player_adj + 1))
-        .try_into()
-        .map_or_else(
-            |err| panic!("Could not convert this line's score to a byte {err}"),
-            |a| a,
-        )
+    let opponent = game.opponent_move(symbol(opponent)?)?;
+    let player = game.player_move(symbol(player)?, opponent)?;
+
+    Ok((opponent, player))
+}
+
+// Tries every assignment of the player's symbols to moves (or outcomes),
+// returning the game with the best assignment along with its total score.
+// The first assignment found wins ties.
+fn optimise_strategy(game: &CyclicGame, file_contents: &str) -> (CyclicGame, Score) {
+    permutations(game.player_symbols())
+        .into_iter()
+        .map(|symbols| {
+            let remapped = game.remap_player(&symbols);
+            let score = total_score(&remapped, file_contents);
+
+            (remapped, score)
+        })
+        .reduce(|best, candidate| {
+            if candidate.1 > best.1 {
+                candidate
+            } else {
+                best
+            }
+        })
+        .unwrap_or_else(|| panic!("The game has no player symbols to remap"))
+}
+
+// Generates every ordering of the given items.
+fn permutations<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    if items.is_empty() {
+        return vec![vec![]];
+    }
+
+    (0..items.len())
+        .flat_map(|i| {
+            // Fixes each item at the front, then permutes the rest.
+            let mut rest = items.to_vec();
+            let first = rest.remove(i);
+
+            permutations(&rest).into_iter().map(move |mut permutation| {
+                permutation.insert(0, first.clone());
+                permutation
+            })
+        })
+        .collect()
 }
 
-pub fn main() {
+pub fn main(args: &[String]) {
+    // Reports the best remapping of the player's column for both interpretations.
+    if args.get(3).map(String::as_str) == Some("optimise") {
+        let file_contents = read_strategy(args.get(4));
+
+        for (name, interpretation) in [
+            ("Moves", Interpretation::Moves),
+            ("Outcomes", Interpretation::Outcomes),
+        ] {
+            let game = CyclicGame::rock_paper_scissors(interpretation);
+            let (best, score) = optimise_strategy(&game, &file_contents);
+
+            println!("{name}: {} -> {score}", best.describe_player_mapping());
+        }
+
+        return;
+    }
+
+    // Lists the points of every round, to see where a total comes from.
+    if args.get(3).map(String::as_str) == Some("breakdown") {
+        let format = match args.get(4).map(String::as_str) {
+            None | Some("table") => Format::Table,
+            Some("csv") => Format::Csv,
+            Some(other) => panic!("Unknown breakdown format '{other}', use 'table' or 'csv'."),
+        };
+
+        let interpretation = match args.get(5).map(String::as_str) {
+            None | Some("outcomes") => Interpretation::Outcomes,
+            Some("moves") => Interpretation::Moves,
+            Some(other) => panic!("Unknown interpretation '{other}', use 'moves' or 'outcomes'."),
+        };
+
+        print!(
+            "{}",
+            score_breakdown(
+                &CyclicGame::rock_paper_scissors(interpretation),
+                &read_strategy(args.get(6)),
+                format,
+            )
+        );
+
+        return;
+    }
+
     println!("{}", rock_paper_scissors(STRATEGY_TEST, true));
     println!("{}", rock_paper_scissors(STRATEGY_REAL, true));
 }
 
+pub fn solve(input: &str) -> Answers {
+    [false, true].map(|decrypt| Some(rock_paper_scissors(input, decrypt).to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -86,4 +201,82 @@ mod tests {
         assert_eq!(12, rock_paper_scissors(STRATEGY_TEST, true));
         assert_eq!(13_600, rock_paper_scissors(STRATEGY_REAL, true));
     }
+
+    #[test]
+    fn optimise() {
+        let (best, score) = optimise_strategy(
+            &CyclicGame::rock_paper_scissors(Interpretation::Moves),
+            STRATEGY_TEST,
+        );
+        assert_eq!(24, score);
+        assert_eq!("Z=A Y=B X=C", best.describe_player_mapping());
+
+        let (best, score) = optimise_strategy(
+            &CyclicGame::rock_paper_scissors(Interpretation::Outcomes),
+            STRATEGY_TEST,
+        );
+        assert_eq!(18, score);
+        assert_eq!("Y=Loss Z=Draw X=Win", best.describe_player_mapping());
+
+        // The real answers are never better than the optimum.
+        let (_, score) = optimise_strategy(
+            &CyclicGame::rock_paper_scissors(Interpretation::Moves),
+            STRATEGY_REAL,
+        );
+        assert!(score >= 11_386);
+    }
+
+    #[test]
+    fn breakdown() {
+        let game = CyclicGame::rock_paper_scissors(Interpretation::Moves);
+
+        assert_eq!(
+            "line,opponent,player,outcome,shape_points,outcome_points,score\n\
+             1,A,B,Win,2,6,8\n\
+             2,B,A,Loss,1,0,1\n\
+             3,C,C,Draw,3,3,6\n",
+            score_breakdown(&game, STRATEGY_TEST, Format::Csv)
+        );
+
+        let table = score_breakdown(&game, STRATEGY_TEST, Format::Table);
+        assert_eq!(5, table.lines().count());
+        assert!(table
+            .lines()
+            .last()
+            .is_some_and(|line| line.ends_with("15")));
+    }
+
+    #[test]
+    fn lizard_spock() {
+        // Rock, Spock, paper, lizard, scissors: each move beats the two before it.
+        let game = CyclicGame::new(
+            vec![1, 2, 3, 4, 5],
+            [0, 3, 6],
+            "RKPLS",
+            "rkpls",
+            Interpretation::Moves,
+        );
+
+        // Spock vaporizes rock, lizard poisons Spock, rock crushes lizard.
+        assert_eq!(2 + 6, play_round(&game, "R k"));
+        assert_eq!(4 + 6, play_round(&game, "K l"));
+        assert_eq!(1 + 6, play_round(&game, "L r"));
+        // Scissors decapitates lizard, paper disproves Spock.
+        assert_eq!(4, play_round(&game, "S l"));
+        assert_eq!(2, play_round(&game, "P k"));
+        assert_eq!(5 + 3, play_round(&game, "S s"));
+        assert_eq!(8 + 10 + 7, total_score(&game, "R k\nK l\nL r"));
+
+        // Aiming for outcomes instead picks the closest move in the cycle.
+        let game = CyclicGame::new(
+            vec![1, 2, 3, 4, 5],
+            [0, 3, 6],
+            "RKPLS",
+            "ldw",
+            Interpretation::Outcomes,
+        );
+
+        assert_eq!(2 + 6, play_round(&game, "R w"));
+        assert_eq!(5, play_round(&game, "R l"));
+    }
 }
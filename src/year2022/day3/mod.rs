@@ -1,6 +1,7 @@
+use crate::{input, solutions::Answers};
 use std::{cmp::Ordering, convert::Into};
 
-// Holds the contents of each of the inputs files.
+// Holds the contents of each of the input files.
 const RUCKSACKS_TEST: &str = include_str!("rucksack-ex.txt");
 const RUCKSACKS_REAL: &str = include_str!("rucksack.txt");
 
@@ -15,9 +16,17 @@ pub fn main() {
     println!("{}", rucksacks_sum(RUCKSACKS_REAL));
 }
 
+// Part two isn't solved yet.
+pub fn solve(input: &str) -> Answers {
+    [Some(rucksacks_sum(input).to_string()), None]
+}
+
 fn rucksacks_sum(file_contents: &str) -> u32 {
     // For each line (rucksack) determine its priority, then take the sum.
-    file_contents.lines().map(rucksack_priority).sum()
+    input::normalise(file_contents)
+        .lines()
+        .map(rucksack_priority)
+        .sum()
 }
 
 fn rucksack_priority(rucksack: &str) -> u32 {
@@ -25,10 +34,8 @@ fn rucksack_priority(rucksack: &str) -> u32 {
         .chars() // Splitting rucksack into individual items (characters).
         .map(|item| {
             // Attempting to convert each item to its corresponding priority number.
-            item_to_priority(item).map_or_else(
-                |err| panic!("Could not map item to priority: {err}"),
-                |item| item,
-            )
+            item_to_priority(item)
+                .unwrap_or_else(|err| panic!("Could not map item to priority: {err}"))
         })
         .collect::<Vec<Priority>>();
 
@@ -1,94 +1,168 @@
+mod sweep;
+
+use crate::{
+    input,
+    interval::Interval,
+    parse::{self, ParseError},
+    solutions::Answers,
+};
 use std::{fmt::Display, str::FromStr};
 
-// Holds the contents of each of the inputs files.
+// Holds the contents of each of the input files.
 const PAIRS_TEST: &str = include_str!("pairs-ex.txt");
 const PAIRS_REAL: &str = include_str!("pairs.txt");
 
 type Pair<T> = (T, T);
 
-pub fn main() {
+pub fn main(args: &[String]) {
+    // Reports coverage across every assignment of a file instead of comparing pairs.
+    if args.get(3).map(String::as_str) == Some("sweep") {
+        let file_contents = input::read_or(args.get(4), || PAIRS_REAL.to_string());
+
+        print_coverage(&sweep::analyse(&parse_assignments(&file_contents)));
+        return;
+    }
+
     println!("{}", pair_comparison(PAIRS_TEST, &range_contains));
     println!("{}", pair_comparison(PAIRS_REAL, &range_contains));
     println!("{}", pair_comparison(PAIRS_TEST, &range_overlaps));
     println!("{}", pair_comparison(PAIRS_REAL, &range_overlaps));
 }
 
+pub fn solve(input: &str) -> Answers {
+    [
+        Some(pair_comparison(input, &range_contains).to_string()),
+        Some(pair_comparison(input, &range_overlaps).to_string()),
+    ]
+}
+
 /// Compares the pairs of elves to compute the amount the pass the comparison.
 /// * `file_contents` - The contents of the file.
 /// * `range_fn` - A function that compares the pair of elves.
-fn pair_comparison(file_contents: &str, range_fn: &dyn Fn(&Pair<Pair<u32>>) -> bool) -> u32 {
-    file_contents
-        .lines() // Splits the list into individual lines of assignments.
-        .map(to_pairs) // Splits line into a pair of two elves.
-        .map(|elves| to_endpoint_pairs(&elves)) // Splits each elf into a pair of endpoints.
-        .map(|(elf_1, elf_2)| {
-            (
-                // Converts each of the pairs of endpoints to integers.
-                parse_endpoints::<u32>(&elf_1),
-                parse_endpoints::<u32>(&elf_2),
-            )
-        })
+fn pair_comparison(file_contents: &str, range_fn: &dyn Fn(&Pair<Interval<u32>>) -> bool) -> u32 {
+    assignments(file_contents)
         .map(|elves| range_fn(&elves)) // Applies custom fn to use for comparing pairs.
         .map(u32::from) // Converts booleans to integers.
         .sum() // Returns total amount of elf pairs that pass 'range_fn'
 }
 
-/// Attempts to split a line by ',' to a pair of elves.
-fn to_pairs(line: &str) -> Pair<String> {
-    line.split_once(',').map_or_else(
-        || panic!("Could not split line into pair, no ',' found."),
-        |pair| (pair.0.to_owned(), pair.1.to_owned()),
-    )
+/// Parses every line of `file_contents` into the pair of intervals assigned to the elves.
+fn parse_assignments(file_contents: &str) -> Vec<Pair<Interval<u32>>> {
+    assignments(file_contents).collect()
 }
 
-/// Attempts to split each elf into a pair of endpoints by '-'
-fn to_endpoint_pairs(elves: &Pair<String>) -> Pair<Pair<String>> {
-    (
-        elves.0.split_once('-').map_or_else(
-            || panic!("Could not split of pair, no '-' found."),
-            |endpoints| (endpoints.0.to_owned(), endpoints.1.to_owned()),
-        ),
-        elves.1.split_once('-').map_or_else(
-            || panic!("Could not split second pair, no '-' found."),
-            |endpoints| (endpoints.0.to_owned(), endpoints.1.to_owned()),
-        ),
-    )
+/// Lazily parses each line of `file_contents` into a pair of intervals, borrowing every
+/// line straight from the input. Line endings and trailing whitespace (like a `\r`)
+/// are trimmed from each line instead, and blank lines are skipped.
+fn assignments(file_contents: &str) -> impl Iterator<Item = Pair<Interval<u32>>> + '_ {
+    file_contents
+        .lines() // Splits the list into individual lines of assignments.
+        .map(input::normalise_line)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            parse_line::<u32>(line).unwrap_or_else(|err| {
+                panic!(
+                    "Could not parse assignments: {}",
+                    err.within(file_contents, line)
+                )
+            })
+        })
+        .map(|(elf_1, elf_2)| (to_interval(elf_1), to_interval(elf_2)))
 }
 
-/// Attempts to parse a pair of endpoints to a defnied type.
-fn parse_endpoints<T>(endpoints: &Pair<String>) -> Pair<T>
+/// Prints the results of a sweep, numbering lines from 1 to match the file.
+fn print_coverage(coverage: &sweep::Coverage) {
+    println!(
+        "Most elves on a section: {} (section {})",
+        coverage.most_elves, coverage.busiest_section
+    );
+
+    let uncovered = coverage
+        .uncovered
+        .iter()
+        .map(|gap| format!("{}-{}", gap.start, gap.end))
+        .collect::<Vec<_>>();
+    println!("Uncovered sections: {}", uncovered.join(", "));
+
+    println!(
+        "Overlapping lines: {} pairs",
+        coverage.overlapping_lines.len()
+    );
+    for (line_1, line_2) in &coverage.overlapping_lines {
+        println!("  {} and {}", line_1 + 1, line_2 + 1);
+    }
+}
+
+/// Parses a line of two assignments, like `2-4,6-8`, into their endpoints.
+/// Only slices of `line` are used along the way, so nothing gets allocated.
+fn parse_line<T>(line: &str) -> Result<Pair<Pair<T>>, ParseError>
 where
-    T: FromStr + Display,
+    T: FromStr,
     <T as FromStr>::Err: Display,
 {
-    (
-        endpoints.0.parse().map_or_else(
-            |err| panic!("Could not convert first endpoint: {err}"),
-            |endpoint| endpoint,
-        ),
-        endpoints.1.parse().map_or_else(
-            |err| panic!("Could not convert second endpoint: {err}"),
-            |endpoint| endpoint,
-        ),
-    )
+    let endpoints = |elf| parse::pair(elf, "-", parse::integer, parse::integer);
+
+    parse::pair(line, ",", endpoints, endpoints)
+}
+
+/// Converts a pair of endpoints to an interval, panics if they are out of order.
+fn to_interval<T: Copy + Ord>((start, end): Pair<T>) -> Interval<T> {
+    Interval::new(start, end)
 }
 
 /// Checks if the range of `elf_1` encompasses `elf_2`,
 /// or if the range of `elf_2` encompasses `elf_1`.
-fn range_contains<T: PartialOrd>((elf_1, elf_2): &Pair<Pair<T>>) -> bool {
-    (elf_1.0 <= elf_2.0) && (elf_2.1 <= elf_1.1) || (elf_2.0 <= elf_1.0 && elf_1.1 <= elf_2.1)
+fn range_contains<T: Copy + Ord>((elf_1, elf_2): &Pair<Interval<T>>) -> bool {
+    elf_1.contains(elf_2) || elf_2.contains(elf_1)
 }
 
-/// Checks for overlapping between `elf_1` and `elf_2` by
-/// checking if the start of `elf_1` is past the end of `elf_2`,
-/// and the end of `elf_2` is past the start o`elf_1`.
-fn range_overlaps<T: PartialOrd>((elf_1, elf_2): &Pair<Pair<T>>) -> bool {
-    (elf_1.1 >= elf_2.0) && (elf_2.1 >= elf_1.0)
+/// Checks for overlapping between `elf_1` and `elf_2`.
+fn range_overlaps<T: Copy + Ord>((elf_1, elf_2): &Pair<Interval<T>>) -> bool {
+    elf_1.overlaps(elf_2)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::{hint::black_box, time::Instant};
+
+    /// The previous parser, which allocated owned strings at every stage,
+    /// kept as a baseline for the parsing benchmark.
+    fn parse_line_owned(line: &str) -> Pair<Pair<u32>> {
+        let split_owned = |str: &str, delim| {
+            let (a, b) = str.split_once(delim).unwrap();
+            (a.to_owned(), b.to_owned())
+        };
+
+        let elves = split_owned(line, ',');
+        let (elf_1, elf_2) = (split_owned(&elves.0, '-'), split_owned(&elves.1, '-'));
+
+        (
+            (elf_1.0.parse().unwrap(), elf_1.1.parse().unwrap()),
+            (elf_2.0.parse().unwrap(), elf_2.1.parse().unwrap()),
+        )
+    }
+
+    /// Generates `lines` random assignment pairs with a fixed seed.
+    fn generate_pairs(lines: usize) -> String {
+        let mut state: u64 = 0x2022_0004;
+        let mut next = |bound: u64| {
+            // Linear congruential generator, good enough for benchmark data.
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 33) % bound
+        };
+
+        (0..lines)
+            .map(|_| {
+                let (start_1, start_2) = (next(1_000), next(1_000));
+                let (end_1, end_2) = (start_1 + next(100), start_2 + next(100));
+                format!("{start_1}-{end_1},{start_2}-{end_2}")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 
     #[test]
     fn part1() {
@@ -101,4 +175,87 @@ mod tests {
         assert_eq!(4, pair_comparison(PAIRS_TEST, &range_overlaps));
         assert_eq!(839, pair_comparison(PAIRS_REAL, &range_overlaps));
     }
+
+    #[test]
+    fn sweep() {
+        let coverage = sweep::analyse(&parse_assignments(PAIRS_TEST));
+        assert_eq!(8, coverage.most_elves);
+        assert_eq!(6, coverage.busiest_section);
+        assert!(coverage.uncovered.is_empty());
+        assert_eq!(15, coverage.overlapping_lines.len());
+
+        // Lines only overlap through different lines, and gaps are reported as intervals.
+        let coverage = sweep::analyse(&parse_assignments("1-3,8-9\n2-2,5-5\n3-3,12-12\n"));
+        assert_eq!(
+            sweep::Coverage {
+                most_elves: 2,
+                busiest_section: 2,
+                uncovered: vec![
+                    Interval::new(4, 4),
+                    Interval::new(6, 7),
+                    Interval::new(10, 11)
+                ],
+                overlapping_lines: vec![(0, 1), (0, 2)],
+            },
+            coverage
+        );
+    }
+
+    #[test]
+    fn borrowed_parsing() {
+        assert_eq!(Ok(((2, 4), (6, 8))), parse_line::<u32>("2-4,6-8"));
+        assert_eq!(Ok(((12, 80), (3, 3))), parse_line::<u64>("12-80,3-3"));
+
+        let err = parse_line::<u32>("2-4,6~8").unwrap_err();
+        assert_eq!((1, 5), (err.line, err.column));
+
+        // Windows line endings and trailing blank lines are handled without normalising first.
+        assert_eq!(
+            pair_comparison(PAIRS_TEST, &range_overlaps),
+            pair_comparison(&PAIRS_TEST.replace('\n', "\r\n"), &range_overlaps)
+        );
+        assert_eq!(
+            1,
+            pair_comparison("\u{feff}2-4,3-5\r\n\r\n", &range_overlaps)
+        );
+
+        let generated = generate_pairs(1_000);
+        assert!(generated
+            .lines()
+            .all(|line| parse_line::<u32>(line) == Ok(parse_line_owned(line))));
+    }
+
+    /// The previous way of counting overlapping pairs, which normalised a copy of the
+    /// whole input before parsing it with owned strings.
+    fn pair_comparison_owned(file_contents: &str) -> u32 {
+        input::normalise(file_contents)
+            .lines()
+            .map(parse_line_owned)
+            .map(|(elf_1, elf_2)| range_overlaps(&(to_interval(elf_1), to_interval(elf_2))))
+            .map(u32::from)
+            .sum()
+    }
+
+    /// Compares counting overlapping pairs the old way against `pair_comparison`,
+    /// on a large generated input with Windows line endings.
+    /// Run with `cargo test --release bench_parsing -- --ignored --nocapture`.
+    #[test]
+    #[ignore = "benchmark, slow without --release"]
+    fn bench_parsing() {
+        let generated = generate_pairs(2_000_000).replace('\n', "\r\n");
+
+        let run = |count: &dyn Fn(&str) -> u32| {
+            let timer = Instant::now();
+            let overlapping = count(black_box(&generated));
+
+            (timer.elapsed(), overlapping)
+        };
+
+        let (owned_time, owned) = run(&pair_comparison_owned);
+        let (borrowed_time, borrowed) =
+            run(&|file_contents| pair_comparison(file_contents, &range_overlaps));
+
+        assert_eq!(owned, borrowed);
+        println!("owned: {owned_time:?}, borrowed: {borrowed_time:?}");
+    }
 }
@@ -0,0 +1,103 @@
+use std::collections::{BTreeSet, HashSet};
+
+use super::Pair;
+use crate::interval::Interval;
+
+/// Results of sweeping over every assignment in a file.
+/// * `most_elves` - Most elves assigned to any single section.
+/// * `busiest_section` - The lowest section reaching `most_elves`.
+/// * `uncovered` - Gaps nobody is assigned to, between the lowest and highest sections.
+/// * `overlapping_lines` - Pairs of line indices (lowest first) with overlapping assignments.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Coverage {
+    pub most_elves: usize,
+    pub busiest_section: u32,
+    pub uncovered: Vec<Interval<u32>>,
+    pub overlapping_lines: Vec<Pair<usize>>,
+}
+
+/// Whether an assignment begins or stops covering sections at an event's position.
+/// Ends sort before starts, so an assignment ending right where another starts never overlaps it.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Event {
+    End,
+    Start,
+}
+
+/// Sweeps across the sections of every assignment in order, so coverage and overlapping
+/// lines are found in O(n log n + k) for `k` overlapping assignments, rather than O(n²).
+pub fn analyse(lines: &[Pair<Interval<u32>>]) -> Coverage {
+    // Each assignment starts at its first section, and ends just past its last one.
+    let mut events = lines
+        .iter()
+        .enumerate()
+        .flat_map(|(line, (elf_1, elf_2))| [(line, 0, elf_1), (line, 1, elf_2)])
+        .flat_map(|(line, elf, interval)| {
+            [
+                (u64::from(interval.start), Event::Start, line, elf),
+                (u64::from(interval.end) + 1, Event::End, line, elf),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    events.sort_unstable();
+
+    let mut active = BTreeSet::new();
+    let mut overlapping = HashSet::new();
+    let mut coverage = Coverage {
+        most_elves: 0,
+        busiest_section: 0,
+        uncovered: vec![],
+        overlapping_lines: vec![],
+    };
+
+    for (i, &(position, event, line, elf)) in events.iter().enumerate() {
+        match event {
+            Event::End => {
+                active.remove(&(line, elf));
+            }
+            Event::Start => {
+                // A new assignment overlaps every assignment still being covered.
+                for &(other_line, _) in &active {
+                    if other_line != line {
+                        overlapping.insert((other_line.min(line), other_line.max(line)));
+                    }
+                }
+
+                active.insert((line, elf));
+            }
+        }
+
+        // Only look at the coverage once every event at this position is processed.
+        let Some(&(next_position, ..)) = events.get(i + 1) else {
+            break;
+        };
+
+        if next_position == position {
+            continue;
+        }
+
+        if active.len() > coverage.most_elves {
+            coverage.most_elves = active.len();
+            coverage.busiest_section = to_section(position);
+        }
+
+        // Nobody covers the sections up until the next event.
+        if active.is_empty() {
+            coverage.uncovered.push(Interval::new(
+                to_section(position),
+                to_section(next_position - 1),
+            ));
+        }
+    }
+
+    coverage.overlapping_lines = overlapping.into_iter().collect();
+    coverage.overlapping_lines.sort_unstable();
+
+    coverage
+}
+
+/// Converts a position back to a section, which always fits since it came from one.
+fn to_section(position: u64) -> u32 {
+    u32::try_from(position).unwrap_or_else(|err| panic!("Position is not a section: {err}"))
+}
@@ -1,11 +1,16 @@
 mod modes;
 mod traits;
 
+use crate::{
+    input,
+    parse::{self, ParseError},
+    solutions::Answers,
+};
 use modes::CraneMode;
 use std::vec;
 use traits::Poppable;
 
-// Holds the contents of each of the inputs files.
+// Holds the contents of each of the input files.
 const INSTRUCTIONS_TEST: &str = include_str!("instructions-ex.txt");
 const INSTRUCTIONS_REAL: &str = include_str!("instructions.txt");
 
@@ -13,7 +18,7 @@ const ITEM_CHAR_SPACING: usize = 4;
 
 type Instruction = usize;
 type Stack = Vec<char>;
-type Procedure = (Instruction, Instruction, Instruction);
+type Procedure = [Instruction; 3];
 
 pub fn main() {
     let mut operator = CraneOperator::new();
@@ -30,6 +35,17 @@ pub fn main() {
     println!("{}", operator.pop_top_string());
 }
 
+// Rearranges the stacks with each crane, reading the crates left on top.
+pub fn solve(input: &str) -> Answers {
+    [CraneMode::Mode9000, CraneMode::Mode9001].map(|mode| {
+        let mut operator = CraneOperator::new();
+
+        operator.load_instructions(input);
+        operator.rearrange_stacks(&mode);
+        Some(operator.pop_top_string())
+    })
+}
+
 /// `CraneOperator` struct.
 /// * `stacks` - Vector of stacks that will be manipulated according to the procuedures.
 /// * `procedures` - Vector of procedures composing of Instructions to be carried out.
@@ -49,31 +65,34 @@ impl CraneOperator {
 
     /// Loads instructions from `file_contents` into `stacks` and `procedures accordingly`.
     fn load_instructions(&mut self, file_contents: &str) {
+        let file_contents = input::normalise(file_contents);
+
         // Seperates the two sections by finding an empty line as a divider.
-        let (items_section, procedures_section) = file_contents.split_once("\n\n").map_or_else(
-            || panic!("Could not split the contents into two segments: No empty line found."),
-            |sections| sections,
-        );
+        let [items_section, procedures_section] =
+            input::paragraphs(&file_contents).collect::<Vec<_>>()[..]
+        else {
+            panic!("Could not split the contents into two segments: No empty line found.");
+        };
 
         self.stacks = vec![Stack::new(); Self::get_stack_count(items_section)];
-        self.procedures = vec![];
 
         Self::load_items(items_section, &mut self.stacks);
-        Self::load_procedures(procedures_section, &mut self.procedures);
+        self.procedures = Self::load_procedures(procedures_section).unwrap_or_else(|err| {
+            panic!(
+                "Could not load procedures: {}",
+                err.within(&file_contents, procedures_section)
+            )
+        });
     }
 
     /// Gets the appropriate amount of stacks to hold the items in `items_section`.
     fn get_stack_count(items_section: &str) -> usize {
-        (items_section
+        items_section
             .lines()
-            .next() // First line of the items section
-            .map_or_else(
-                || panic!("Could not read contents of the 'items' segment"),
-                |line| line,
-            )
-            .len()
-            + 1)
-            / ITEM_CHAR_SPACING // Dividing by the space between the items yields the count.
+            .next_back() // Last line of the items section holds the stack labels.
+            .unwrap_or_else(|| panic!("Could not read contents of the 'items' segment"))
+            .split_whitespace()
+            .count() // Each label corresponds to a stack.
     }
 
     /// Loads items from `items_section` into `stacks`.
@@ -81,10 +100,9 @@ impl CraneOperator {
         let mut push_item = |i: usize, item: char| {
             stacks
                 .get_mut(i)
-                .map_or_else(
-                    || panic!("Attempted to index out of bounds item when loading items"),
-                    |stack| stack,
-                )
+                .unwrap_or_else(|| {
+                    panic!("Attempted to index out of bounds item when loading items")
+                })
                 .push(item);
         };
 
@@ -103,36 +121,16 @@ impl CraneOperator {
         }
     }
 
-    /// Loads procedures from `procedures_section` into `procedures`.
-    fn load_procedures(procedures_section: &str, procedures: &mut Vec<Procedure>) {
-        let parse_instruction = |instruction: &str| {
-            instruction.parse::<Instruction>().map_or_else(
-                |err| panic!("Could not convert string {instruction} to instruction: {err}"),
-                |converted| converted,
-            )
-        };
-
-        let preprocess_line = |line: &str| -> Procedure {
-            match line
-                .split(' ')
-                .skip(1) // Skips first word.
-                .step_by(2) // Skips other words.
-                .map(parse_instruction)
-                .collect::<Vec<_>>()[..]
-            {
-                [moves, source, destination] => (moves, source, destination),
-                _ => panic!("Procedure must have exactly three instructions."),
-            }
-        };
-
-        for line in procedures_section.lines() {
-            procedures.push(preprocess_line(line));
-        }
+    /// Loads procedures from `procedures_section`, each line being like `move 1 from 2 to 3`.
+    fn load_procedures(procedures_section: &str) -> Result<Vec<Procedure>, ParseError> {
+        parse::lines(procedures_section, |line| {
+            parse::integers(line, "move {} from {} to {}")
+        })
     }
 
     fn rearrange_stacks(&mut self, mode: &CraneMode) {
-        for (moves, source, destination) in &self.procedures {
-            mode.perform_procedure(&mut self.stacks, *moves, *source, *destination);
+        for &[moves, source, destination] in &self.procedures {
+            mode.perform_procedure(&mut self.stacks, moves, source, destination);
         }
     }
 
@@ -175,4 +173,14 @@ mod tests {
         operator.rearrange_stacks(&mode);
         assert_eq!("LVZPSTTCZ", operator.pop_top_string());
     }
+
+    #[test]
+    fn messy_input() {
+        let mut operator = CraneOperator::new();
+
+        // Windows line endings also bring trailing whitespace off the item lines.
+        operator.load_instructions(&INSTRUCTIONS_TEST.replace('\n', "\r\n"));
+        operator.rearrange_stacks(&CraneMode::Mode9000);
+        assert_eq!("CMZ", operator.pop_top_string());
+    }
 }
@@ -22,10 +22,7 @@ impl CraneMode {
     fn append_to_stack(stacks: &mut [Stack], popped: &mut Stack, destination: usize) {
         stacks
             .get_mut(destination - 1)
-            .map_or_else(
-                || panic!("Could not get destination stack during move"),
-                |dest| dest,
-            )
+            .unwrap_or_else(|| panic!("Could not get destination stack during move"))
             .append(popped);
     }
 
@@ -7,7 +7,6 @@ pub trait Poppable {
 impl Poppable for Stack {
     /// Attempts to pop off an item, panics with message if unsuccessful.
     fn try_pop(&mut self, err_msg: &str) -> char {
-        self.pop()
-            .map_or_else(|| panic!("{}", err_msg), |popped| popped)
+        self.pop().unwrap_or_else(|| panic!("{}", err_msg))
     }
 }
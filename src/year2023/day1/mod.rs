@@ -1,59 +1,76 @@
+mod vocabulary;
+
+use crate::{input, solutions::Answers};
+use vocabulary::Vocabulary;
+
 const DOCUMENT_TEST_1: &str = include_str!("calibration-ex1.txt");
 const DOCUMENT_TEST_2: &str = include_str!("calibration-ex2.txt");
 const DOCUMENT_REAL: &str = include_str!("calibration.txt");
 
-const DIGIT_WORDS: [&str; 9] = [
-    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-];
-
-pub fn main() {
-    println!("{}", calval_sum(DOCUMENT_TEST_1));
-    println!("{}", calval_sum(DOCUMENT_TEST_2));
-    println!("{}", calval_sum(DOCUMENT_REAL));
-}
-
-fn replace_words(line: &str) -> String {
-    let mut replaced_line = line.to_string();
-
-    // (Found Index, Digit to fill)
-    let mut found_words: Vec<(usize, usize)> = Vec::new();
-
-    for (word_idx, word) in DIGIT_WORDS.iter().enumerate() {
-        let finds = replaced_line.match_indices(word);
+pub fn main(args: &[String]) {
+    // Part one only counts digits, part two also counts words, which can be
+    // picked by language or given as 'word=digit' pairs.
+    match args.get(3).map(String::as_str) {
+        Some("digits") => print_sum(&read_document(args.get(4)), &Vocabulary::digits()),
+        Some("words") => {
+            let vocabulary = match args.get(4).map(String::as_str) {
+                None | Some("english") => Vocabulary::new(&vocabulary::ENGLISH),
+                Some("french") => Vocabulary::new(&vocabulary::FRENCH),
+                Some("german") => Vocabulary::new(&vocabulary::GERMAN),
+                Some(spec) => Vocabulary::parse(spec),
+            }
+            .unwrap_or_else(|err| panic!("Invalid vocabulary: {err}"));
+
+            print_sum(&read_document(args.get(5)), &vocabulary);
+        }
+        _ => {
+            let (digits, english) = (Vocabulary::digits(), Vocabulary::english());
 
-        for (find_idx, _) in finds {
-            found_words.push((find_idx, word_idx + 1));
+            print_sum(DOCUMENT_TEST_1, &digits);
+            print_sum(DOCUMENT_REAL, &digits);
+            print_sum(DOCUMENT_TEST_2, &english);
+            print_sum(DOCUMENT_REAL, &english);
         }
     }
+}
 
-    found_words.sort_by(|a, b| a.0.cmp(&b.0));
+pub fn solve(input: &str) -> Answers {
+    [Vocabulary::digits(), Vocabulary::english()].map(|vocabulary| {
+        let sum = calval_sum(input, &vocabulary)
+            .unwrap_or_else(|err| panic!("Could not read the calibration values: {err}"));
 
-    for (i, (find_idx, digit)) in found_words.iter().enumerate() {
-        replaced_line.insert_str(find_idx + i, digit.to_string().as_str());
-    }
+        Some(sum.to_string())
+    })
+}
 
-    replaced_line
+// Reads the document at the path, or the real document without one.
+fn read_document(path: Option<&String>) -> String {
+    input::read_or(path, || DOCUMENT_REAL.to_string())
 }
 
-fn line_to_num(line: &str) -> u32 {
-    let nums = line
-        .chars()
-        .filter(char::is_ascii_digit)
-        .collect::<String>();
+fn print_sum(document: &str, vocabulary: &Vocabulary) {
+    match calval_sum(document, vocabulary) {
+        Ok(sum) => println!("{sum}"),
+        Err(err) => println!("Could not read the calibration values: {err}"),
+    }
+}
 
-    let first_digit = nums.chars().next().unwrap_or('0');
-    let last_digit = nums.chars().next_back().unwrap_or('0');
+// Combines the first and last digit tokens of the line into a two digit number.
+fn line_to_num(line: &str, vocabulary: &Vocabulary) -> Result<u32, String> {
+    let (first, last) = vocabulary
+        .first_and_last(line)
+        .ok_or_else(|| format!("No digits in line '{line}'!"))?;
 
-    format!("{first_digit}{last_digit}").parse().unwrap_or(0)
+    Ok(first.digit * 10 + last.digit)
 }
 
-fn calval_sum(file_contents: &str) -> u32 {
-    file_contents
+fn calval_sum(file_contents: &str, vocabulary: &Vocabulary) -> Result<u32, String> {
+    input::normalise(file_contents)
         .lines()
-        .map(replace_words)
-        .map(|line| line_to_num(line.as_str()))
-        .collect::<Vec<u32>>()
-        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            line_to_num(line, vocabulary).map_err(|err| format!("Line {}: {err}", idx + 1))
+        })
         .sum()
 }
 
@@ -63,13 +80,52 @@ mod tests {
 
     #[test]
     fn part1() {
-        assert_eq!(calval_sum(DOCUMENT_TEST_1), 142);
-        // assert_eq!(calval_sum(DOCUMENT_REAL), 53_194);
+        let digits = Vocabulary::digits();
+        assert_eq!(calval_sum(DOCUMENT_TEST_1, &digits), Ok(142));
+        assert_eq!(calval_sum(DOCUMENT_REAL, &digits), Ok(53_194));
     }
 
     #[test]
     fn part2() {
-        assert_eq!(calval_sum(DOCUMENT_TEST_2), 281);
-        assert_eq!(calval_sum(DOCUMENT_REAL), 54_249);
+        let english = Vocabulary::english();
+        assert_eq!(calval_sum(DOCUMENT_TEST_2, &english), Ok(281));
+        assert_eq!(calval_sum(DOCUMENT_REAL, &english), Ok(54_249));
+    }
+
+    #[test]
+    fn overlapping_words() {
+        let english = Vocabulary::english();
+        assert_eq!(line_to_num("twone", &english), Ok(21));
+        assert_eq!(line_to_num("eightwo", &english), Ok(82));
+        assert_eq!(line_to_num("oneight3sevenine", &english), Ok(19));
+        assert_eq!(line_to_num("x7x", &english), Ok(77));
+
+        // Lines without any digits are errors instead of counting as zero.
+        assert!(line_to_num("abc", &english).is_err());
+        assert!(line_to_num("eightwo", &Vocabulary::digits()).is_err());
+        assert_eq!(
+            calval_sum("1abc2\nnothing\n", &english),
+            Err("Line 2: No digits in line 'nothing'!".to_string())
+        );
+    }
+
+    #[test]
+    fn vocabularies() {
+        let french = Vocabulary::new(&vocabulary::FRENCH).unwrap();
+        assert_eq!(line_to_num("xdeuxseptquatrex", &french), Ok(24));
+        assert_eq!(line_to_num("zéroneuf", &french), Ok(9));
+
+        let german = Vocabulary::new(&vocabulary::GERMAN).unwrap();
+        assert_eq!(line_to_num("fünfzweiundsechs", &german), Ok(56));
+
+        // Custom aliases, where longer words starting at the same place win.
+        let custom = Vocabulary::parse("zero=0, one=1, oneteen=7, ten=0").unwrap();
+        assert_eq!(line_to_num("onetenzero", &custom), Ok(10));
+        assert_eq!(line_to_num("xoneteenzero", &custom), Ok(70));
+        assert!(line_to_num("two", &custom).is_err());
+
+        assert!(Vocabulary::parse("one=1, one=2").is_err());
+        assert!(Vocabulary::parse("eleven=11").is_err());
+        assert!(Vocabulary::parse("=1").is_err());
     }
 }
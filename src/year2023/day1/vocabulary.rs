@@ -0,0 +1,249 @@
+use std::{
+    cmp::{self, Reverse},
+    collections::{HashMap, VecDeque},
+};
+
+pub const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+pub const FRENCH: [(&str, u32); 10] = [
+    ("zéro", 0),
+    ("un", 1),
+    ("deux", 2),
+    ("trois", 3),
+    ("quatre", 4),
+    ("cinq", 5),
+    ("six", 6),
+    ("sept", 7),
+    ("huit", 8),
+    ("neuf", 9),
+];
+
+pub const GERMAN: [(&str, u32); 10] = [
+    ("null", 0),
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+];
+
+/// A digit token found in a line.
+/// * `start` - Byte index the token starts at.
+/// * `len` - Length of the token in bytes.
+/// * `digit` - The digit the token stands for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub start: usize,
+    pub len: usize,
+    pub digit: u32,
+}
+
+/// A node of the matching automaton.
+/// * `next` - Transitions to the following nodes by byte.
+/// * `fail` - Node for the longest proper suffix that is also in the trie.
+/// * `word` - Length and digit of the word ending at this node, if any.
+/// * `output` - Closest node along the fail links that ends a word.
+#[derive(Default)]
+struct Node {
+    next: HashMap<u8, usize>,
+    fail: usize,
+    word: Option<(usize, u32)>,
+    output: Option<usize>,
+}
+
+/// The words (and digit characters) that count as digits, matched with an
+/// Aho-Corasick automaton so scanning a line stays linear however many words there are.
+pub struct Vocabulary {
+    nodes: Vec<Node>,
+}
+
+impl Vocabulary {
+    /// Builds the automaton for the digits `0` to `9` along with the given words.
+    /// Fails if a word is empty or stands for more than one digit.
+    pub fn new(words: &[(&str, u32)]) -> Result<Self, String> {
+        let mut vocabulary = Self {
+            nodes: vec![Node::default()],
+        };
+
+        let digit_chars = ('0'..='9')
+            .zip(0..)
+            .map(|(char, digit)| (char.to_string(), digit));
+
+        for (word, digit) in digit_chars.chain(words.iter().map(|&(w, d)| (w.to_string(), d))) {
+            vocabulary.insert(&word, digit)?;
+        }
+
+        vocabulary.link_failures();
+        Ok(vocabulary)
+    }
+
+    /// Parses custom words written like `one=1, uno=1, eins=1`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let words = spec
+            .split([',', '\n'])
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (word, digit) = entry
+                    .split_once('=')
+                    .ok_or_else(|| format!("Expected 'word=digit', found '{entry}'!"))?;
+
+                let digit = digit
+                    .trim()
+                    .parse()
+                    .map_err(|err| format!("Could not parse digit of '{entry}'! ({err})"))?;
+
+                Ok((word.trim(), digit))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Self::new(&words)
+    }
+
+    /// Only the digit characters, without any words.
+    pub fn digits() -> Self {
+        Self::new(&[]).unwrap_or_else(|err| panic!("Invalid digits: {err}"))
+    }
+
+    /// The digit characters along with the English words from `one` to `nine`.
+    pub fn english() -> Self {
+        Self::new(&ENGLISH).unwrap_or_else(|err| panic!("Invalid English words: {err}"))
+    }
+
+    /// Adds a word to the trie.
+    fn insert(&mut self, word: &str, digit: u32) -> Result<(), String> {
+        if word.is_empty() {
+            return Err("Digit words can't be empty!".to_string());
+        }
+
+        if digit > 9 {
+            return Err(format!(
+                "Word '{word}' stands for {digit}, which isn't a digit!"
+            ));
+        }
+
+        let mut node = 0;
+
+        for &byte in word.as_bytes() {
+            node = if let Some(&next) = self.nodes[node].next.get(&byte) {
+                next
+            } else {
+                self.nodes.push(Node::default());
+                let next = self.nodes.len() - 1;
+                self.nodes[node].next.insert(byte, next);
+                next
+            };
+        }
+
+        match self.nodes[node].word {
+            Some((_, existing)) if existing != digit => Err(format!(
+                "Word '{word}' stands for both {existing} and {digit}!"
+            )),
+            _ => {
+                self.nodes[node].word = Some((word.len(), digit));
+                Ok(())
+            }
+        }
+    }
+
+    /// Links every node to its longest proper suffix in the trie, breadth first.
+    fn link_failures(&mut self) {
+        let mut queue = self.nodes[0]
+            .next
+            .values()
+            .copied()
+            .collect::<VecDeque<_>>();
+
+        while let Some(node) = queue.pop_front() {
+            let children = self.nodes[node]
+                .next
+                .iter()
+                .map(|(&byte, &child)| (byte, child))
+                .collect::<Vec<_>>();
+
+            for (byte, child) in children {
+                // Follows the parent's fail links until one can continue with the same byte.
+                let mut fail = self.nodes[node].fail;
+                while fail != 0 && !self.nodes[fail].next.contains_key(&byte) {
+                    fail = self.nodes[fail].fail;
+                }
+
+                let fail = self.nodes[fail]
+                    .next
+                    .get(&byte)
+                    .copied()
+                    .filter(|&next| next != child)
+                    .unwrap_or(0);
+
+                self.nodes[child].fail = fail;
+                self.nodes[child].output = if self.nodes[fail].word.is_some() {
+                    Some(fail)
+                } else {
+                    self.nodes[fail].output
+                };
+
+                queue.push_back(child);
+            }
+        }
+    }
+
+    /// Finds every token in the line, including overlapping ones, in order of where they end.
+    pub fn tokens<'a>(&'a self, line: &'a str) -> impl Iterator<Item = Token> + 'a {
+        let mut node = 0;
+
+        line.bytes().enumerate().flat_map(move |(idx, byte)| {
+            while node != 0 && !self.nodes[node].next.contains_key(&byte) {
+                node = self.nodes[node].fail;
+            }
+            node = self.nodes[node].next.get(&byte).copied().unwrap_or(0);
+
+            // Every word ending here is either at this node or further along the output links.
+            let first = if self.nodes[node].word.is_some() {
+                Some(node)
+            } else {
+                self.nodes[node].output
+            };
+
+            std::iter::successors(first, |&found| self.nodes[found].output).filter_map(
+                move |found| {
+                    self.nodes[found].word.map(|(len, digit)| Token {
+                        start: idx + 1 - len,
+                        len,
+                        digit,
+                    })
+                },
+            )
+        })
+    }
+
+    /// Gets the first and last tokens of the line by where they start,
+    /// preferring the longer token when two start at the same place.
+    pub fn first_and_last(&self, line: &str) -> Option<(Token, Token)> {
+        let mut found: Option<(Token, Token)> = None;
+
+        for token in self.tokens(line) {
+            found = Some(found.map_or((token, token), |(first, last)| {
+                (
+                    cmp::min_by_key(first, token, |token| (token.start, Reverse(token.len))),
+                    cmp::max_by_key(last, token, |token| (token.start, token.len)),
+                )
+            }));
+        }
+
+        found
+    }
+}
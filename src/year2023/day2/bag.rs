@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use super::game::Group;
+
+/// The cubes loaded into the bag, as the most cubes available per colour.
+pub struct Bag {
+    limits: HashMap<String, u32>,
+}
+
+impl Bag {
+    /// Parses a bag written like a round of the game, e.g. `12 red, 13 green, 14 blue`.
+    /// Colours may also be separated by new lines, so a bag can be read from a file.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut limits = HashMap::new();
+
+        for group_str in spec
+            .split([',', '\n'])
+            .map(str::trim)
+            .filter(|group| !group.is_empty())
+        {
+            let group = Group::from_group_str(group_str)
+                .map_err(|err| err.within(spec, group_str).to_string())?;
+
+            if limits.insert(group.color.clone(), group.amount).is_some() {
+                return Err(format!("Colour '{}' is in the bag twice!", group.color));
+            }
+        }
+
+        if limits.is_empty() {
+            return Err("The bag has no colours in it!".to_string());
+        }
+
+        Ok(Self { limits })
+    }
+
+    /// Every colour the bag has cubes of.
+    pub fn colors(&self) -> impl Iterator<Item = &str> {
+        self.limits.keys().map(String::as_str)
+    }
+
+    /// Gets the amount of cubes of the group's colour in the bag,
+    /// failing if the bag doesn't have that colour at all.
+    pub fn limit(&self, group: &Group) -> Result<u32, String> {
+        self.limits
+            .get(&group.color)
+            .copied()
+            .ok_or_else(|| format!("Colour '{}' is not in the bag!", group.color))
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use super::bag::Bag;
+use crate::parse::{self, ParseError};
+
+/// An amount of cubes of a single colour shown at once.
+pub struct Group {
+    pub amount: u32,
+    pub color: String,
+}
+
+impl Group {
+    /// Parses a group written like `3 blue`.
+    pub fn from_group_str(group_str: &str) -> Result<Self, ParseError> {
+        let trimmed = group_str.trim();
+
+        let (amount, color) =
+            parse::pair(trimmed, " ", parse::integer, |color| match color.trim() {
+                "" => Err(ParseError::new("Expected a colour after the amount")),
+                color => Ok(color.to_string()),
+            })
+            .map_err(|err| err.within(group_str, trimmed))?;
+
+        Ok(Self { amount, color })
+    }
+}
+
+/// The most cubes of a colour shown in a game, and the first round (from 0) that showed them.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Requirement {
+    pub amount: u32,
+    pub round: usize,
+}
+
+/// A group of a round (from 0) that had more cubes than the bag holds.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Violation<'a> {
+    pub round: usize,
+    pub color: &'a str,
+    pub amount: u32,
+    pub limit: u32,
+}
+
+/// A single game, with the groups shown in each of its rounds.
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Vec<Group>>,
+}
+
+impl Game {
+    /// Parses a line like `Game 4: 1 green, 3 red; 6 blue`, keeping the id it was given.
+    pub fn parse(line: &str) -> Result<Self, ParseError> {
+        let (header, rounds) = parse::key_value(line, |plays| {
+            parse::list(plays, ";", |round| {
+                parse::list(round, ",", Group::from_group_str)
+            })
+        })?;
+
+        let id_str = header.strip_prefix("Game ").ok_or_else(|| {
+            ParseError::new("Expected 'Game <id>' before ':'").within(line, header)
+        })?;
+        let id = parse::integer(id_str).map_err(|err| err.within(line, id_str))?;
+
+        Ok(Self { id, rounds })
+    }
+
+    /// Iterates through every group of every round, along with the round it is in.
+    pub fn groups(&self) -> impl Iterator<Item = (usize, &Group)> {
+        self.rounds
+            .iter()
+            .enumerate()
+            .flat_map(|(round, groups)| groups.iter().map(move |group| (round, group)))
+    }
+
+    /// Finds every group that had more cubes than the bag holds,
+    /// failing if the game has a colour the bag doesn't know about.
+    pub fn violations(&self, bag: &Bag) -> Result<Vec<Violation<'_>>, String> {
+        let mut violations = vec![];
+
+        for (round, group) in self.groups() {
+            let limit = bag.limit(group)?;
+
+            if group.amount > limit {
+                violations.push(Violation {
+                    round,
+                    color: &group.color,
+                    amount: group.amount,
+                    limit,
+                });
+            }
+        }
+
+        Ok(violations)
+    }
+
+    /// Checks if every group of the game could have been drawn from the bag,
+    /// failing if the game has a colour the bag doesn't know about.
+    pub fn is_possible(&self, bag: &Bag) -> Result<bool, String> {
+        Ok(self.violations(bag)?.is_empty())
+    }
+
+    /// Gets the smallest bag the game could have been played with: the most cubes
+    /// shown of each colour, along with the round that forced it.
+    pub fn minimal_bag(&self) -> BTreeMap<&str, Requirement> {
+        let mut minimal_bag = BTreeMap::new();
+
+        for (round, group) in self.groups() {
+            let requirement = minimal_bag
+                .entry(group.color.as_str())
+                .or_insert(Requirement { amount: 0, round });
+
+            // Only a strictly larger amount moves the requirement to a later round.
+            if group.amount > requirement.amount {
+                *requirement = Requirement {
+                    amount: group.amount,
+                    round,
+                };
+            }
+        }
+
+        minimal_bag
+    }
+
+    /// The product of the most cubes shown of each colour of the bag, along with any other
+    /// colour the game shows. A colour of the bag that never shows up counts as 0 cubes.
+    pub fn power(&self, bag: &Bag) -> u32 {
+        let minimal_bag = self.minimal_bag();
+
+        bag.colors()
+            .filter(|color| !minimal_bag.contains_key(color))
+            .map(|_| 0)
+            .chain(minimal_bag.values().map(|requirement| requirement.amount))
+            .product()
+    }
+}
@@ -1,152 +1,252 @@
-use std::collections::HashMap;
+mod bag;
+mod game;
+
+use crate::{input, parse, solutions::Answers};
+use bag::Bag;
+use game::Game;
+use std::collections::HashSet;
 
 const DOCUMENT_TEST: &str = include_str!("games-ex.txt");
 const DOCUMENT_REAL: &str = include_str!("games.txt");
 
-#[derive(Eq, Hash, PartialEq, Debug)]
-enum Color {
-    Red,
-    Green,
-    Blue,
-}
+// The bag from the puzzle, used when no other bag is given.
+const ELF_BAG: &str = "12 red, 13 green, 14 blue";
 
-struct Group {
-    amount: u32,
-    color: Color,
-}
+// Parses every game of the list once, making sure no two games share an id.
+fn parse_games(game_list: &str) -> Result<Vec<Game>, String> {
+    let game_list = input::normalise(game_list);
+    let games = parse::lines(&game_list, Game::parse).map_err(|err| err.to_string())?;
 
-impl Group {
-    fn from_group_str(group_str: &str) -> Self {
-        let (amount_str, color_str) = attempt_split_once(group_str, ' ');
+    let mut seen = HashSet::new();
 
-        let amount: u32 = amount_str.parse().map_or_else(
-            |err| panic!("Could not parse amount '{amount_str}' to u32! ({err})"),
-            |amount| amount,
-        );
+    for game in &games {
+        if !seen.insert(game.id) {
+            return Err(format!("Game {} appears more than once!", game.id));
+        }
+    }
 
-        let color = match color_str {
-            "red" => Color::Red,
-            "green" => Color::Green,
-            "blue" => Color::Blue,
-            _ => panic!("Could not assign colour to group '{group_str}'!"),
-        };
+    Ok(games)
+}
 
-        Self { amount, color }
-    }
+// Sums the ids of every game that is possible with the given bag.
+fn game_id_sum(game_list: &str, bag: &Bag) -> Result<u32, String> {
+    let mut sum = 0;
 
-    fn is_possible(&self) -> Result<(), String> {
-        if match self.color {
-            Color::Red => self.amount <= 12,
-            Color::Green => self.amount <= 13,
-            Color::Blue => self.amount <= 14,
-        } {
-            Ok(())
-        } else {
-            Err("Group is not possible.".to_string())
+    for game in parse_games(game_list)? {
+        if game.is_possible(bag)? {
+            sum += game.id;
         }
     }
+
+    Ok(sum)
 }
 
-fn id_to_u32(id: usize) -> u32 {
-    u32::try_from(id).map_or_else(
-        |err| panic!("Could not convert id from usize to u32 ({err})"),
-        |id| id,
-    )
+// Sums the power of every game, over the colours of the bag.
+fn game_power(game_list: &str, bag: &Bag) -> Result<u32, String> {
+    Ok(parse_games(game_list)?
+        .iter()
+        .map(|game| game.power(bag))
+        .sum())
 }
 
-fn attempt_split_once(str: &str, delim: char) -> (&str, &str) {
-    let Some((a, b)) = str.split_once(delim) else {
-        panic!("Could not find character '{delim}' to split string '{str}'");
-    };
+// Describes the minimal bag and power of every game, and for every impossible game,
+// the rounds (numbered from 1) and colours that went over the bag.
+fn game_report(game_list: &str, bag: &Bag) -> Result<String, String> {
+    let mut lines = vec![];
 
-    (a, b)
-}
+    for game in parse_games(game_list)? {
+        let minimal_bag = game
+            .minimal_bag()
+            .iter()
+            .map(|(color, req)| format!("{} {color} (round {})", req.amount, req.round + 1))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        lines.push(format!(
+            "Game {}: power {}, minimal bag {minimal_bag}",
+            game.id,
+            game.power(bag)
+        ));
+
+        for violation in game.violations(bag)? {
+            lines.push(format!(
+                "  impossible: round {} has {} {} but the bag holds {}",
+                violation.round + 1,
+                violation.amount,
+                violation.color,
+                violation.limit
+            ));
+        }
+    }
 
-fn game_id_sum(game_list: &str) -> u32 {
-    game_list
-        .lines()
-        .map(game_possible)
-        .enumerate()
-        .filter(|(_, item)| item.is_ok())
-        .map(|(id, _)| id_to_u32(id + 1))
-        .sum()
+    Ok(lines.into_iter().map(|line| line + "\n").collect())
 }
 
-fn game_power(game_list: &str) -> u32 {
-    game_list
-        .lines()
-        .map(max_of_colors)
-        .map(|max_by_color| max_by_color.values().product::<u32>())
-        .sum()
-}
+pub fn main(args: &[String]) {
+    // The bag can be given inline, or read from a file, to sum ids or to print a report.
+    let (bag_spec, report) = match args.get(3).map(String::as_str) {
+        Some("bag") => (args.get(4).cloned(), false),
+        Some("bag-file") => (args.get(4).map(input::read), false),
+        Some("report") => (args.get(4).cloned(), true),
+        Some("report-file") => (args.get(4).map(input::read), true),
+        _ => (None, false),
+    };
 
-fn game_possible(game: &str) -> Result<(), String> {
-    let (_, plays) = attempt_split_once(game, ':');
+    if let Some(bag_spec) = bag_spec {
+        let bag = Bag::parse(&bag_spec).unwrap_or_else(|err| panic!("Invalid bag: {err}"));
+        let game_list = input::read_or(args.get(5), || DOCUMENT_REAL.to_string());
 
-    plays
-        .split(';')
-        .flat_map(|play| play.split(','))
-        .try_for_each(|group| Group::from_group_str(group.trim()).is_possible())
-}
+        if report {
+            match game_report(&game_list, &bag) {
+                Ok(report) => print!("{report}"),
+                Err(err) => println!("Could not check the games: {err}"),
+            }
+        } else {
+            match game_id_sum(&game_list, &bag) {
+                Ok(sum) => println!("{sum}"),
+                Err(err) => println!("Could not check the games: {err}"),
+            }
+        }
 
-fn max_of_colors(game: &str) -> HashMap<Color, u32> {
-    let (_, plays) = attempt_split_once(game, ':');
-
-    let mut groups_by_color: HashMap<Color, Vec<Group>> = vec![
-        (Color::Red, vec![]),
-        (Color::Green, vec![]),
-        (Color::Blue, vec![]),
-    ]
-    .into_iter()
-    .collect();
-
-    let mut max_by_color: HashMap<Color, u32> = HashMap::new();
-
-    for group in plays
-        .split(';')
-        .flat_map(|play| play.split(','))
-        .map(|group| Group::from_group_str(group.trim()))
-    {
-        let Some(color_list) = groups_by_color.get_mut(&group.color) else {
-            panic!("Could not find key '{:?}' in color groups.", group.color);
-        };
-
-        color_list.push(group);
+        return;
     }
 
-    for (color, groups) in groups_by_color {
-        let maximum = groups
-            .iter()
-            .max_by_key(|g| g.amount)
-            .map_or(0, |group| group.amount);
+    let bag = Bag::parse(ELF_BAG).unwrap_or_else(|err| panic!("Invalid bag: {err}"));
 
-        max_by_color.insert(color, maximum);
+    for game_list in [DOCUMENT_TEST, DOCUMENT_REAL] {
+        match game_id_sum(game_list, &bag) {
+            Ok(sum) => println!("{sum}"),
+            Err(err) => println!("Could not check the games: {err}"),
+        }
     }
 
-    max_by_color
+    for game_list in [DOCUMENT_TEST, DOCUMENT_REAL] {
+        match game_power(game_list, &bag) {
+            Ok(power) => println!("{power}"),
+            Err(err) => println!("Could not read the games: {err}"),
+        }
+    }
 }
 
-pub fn main() {
-    println!("{}", game_id_sum(DOCUMENT_TEST));
-    println!("{}", game_id_sum(DOCUMENT_REAL));
+pub fn solve(input: &str) -> Answers {
+    let bag = Bag::parse(ELF_BAG).unwrap_or_else(|err| panic!("Invalid bag: {err}"));
 
-    println!("{}", game_power(DOCUMENT_TEST));
-    println!("{}", game_power(DOCUMENT_REAL));
+    [game_id_sum(input, &bag), game_power(input, &bag)].map(|answer| {
+        let answer = answer.unwrap_or_else(|err| panic!("Could not check the games: {err}"));
+        Some(answer.to_string())
+    })
 }
 
 #[cfg(test)]
 mod tests {
+    use super::game::{Requirement, Violation};
     use super::*;
 
     #[test]
     fn part1() {
-        assert_eq!(game_id_sum(DOCUMENT_TEST), 8);
-        assert_eq!(game_id_sum(DOCUMENT_REAL), 2162);
+        let bag = Bag::parse(ELF_BAG).unwrap();
+        assert_eq!(game_id_sum(DOCUMENT_TEST, &bag), Ok(8));
+        assert_eq!(game_id_sum(DOCUMENT_REAL, &bag), Ok(2162));
     }
 
     #[test]
     fn part2() {
-        assert_eq!(game_power(DOCUMENT_TEST), 2286);
-        assert_eq!(game_power(DOCUMENT_REAL), 72_513);
+        let bag = Bag::parse(ELF_BAG).unwrap();
+        assert_eq!(game_power(DOCUMENT_TEST, &bag), Ok(2286));
+        assert_eq!(game_power(DOCUMENT_REAL, &bag), Ok(72_513));
+    }
+
+    #[test]
+    fn custom_bags() {
+        // A bigger bag allows more games, and any colour names work.
+        let bag = Bag::parse("20 red\n13 green\n15 blue\n").unwrap();
+        assert_eq!(game_id_sum(DOCUMENT_TEST, &bag), Ok(1 + 2 + 3 + 4 + 5));
+
+        let bag = Bag::parse("1 cyan, 2 magenta").unwrap();
+        assert_eq!(
+            game_id_sum("Game 1: 1 cyan; 2 magenta\nGame 2: 2 cyan", &bag),
+            Ok(1)
+        );
+
+        // Colours missing from the bag are errors rather than impossible games.
+        let bag = Bag::parse("12 red, 13 green").unwrap();
+        assert!(game_id_sum(DOCUMENT_TEST, &bag).is_err());
+
+        assert!(Bag::parse("").is_err());
+        assert!(Bag::parse("1 red, 2 red").is_err());
+        assert!(Bag::parse("lots of red").is_err());
+    }
+
+    #[test]
+    fn game_ids() {
+        let bag = Bag::parse(ELF_BAG).unwrap();
+
+        // Reordered and non-contiguous ids are summed as written.
+        let games = "Game 7: 1 red\nGame 3: 13 red\nGame 10: 2 blue; 3 green\n";
+        assert_eq!(game_id_sum(games, &bag), Ok(17));
+
+        // None of these show every colour of the bag, and a missing colour makes the power 0.
+        assert_eq!(game_power(games, &bag), Ok(0));
+        assert_eq!(game_power("Game 1: 2 blue; 3 green, 4 red", &bag), Ok(24));
+
+        assert!(game_id_sum("Game 1: 1 red\nGame 1: 2 red", &bag).is_err());
+        assert!(game_id_sum("1 red, 2 blue", &bag).is_err());
+        assert!(game_id_sum("Game one: 1 red", &bag).is_err());
+
+        // Errors point at where the game went wrong.
+        assert_eq!(
+            game_id_sum("Game 1: 1 red\nGame 3: 1 red; 2 blue, x green", &bag),
+            Err(
+                "line 2, column 24: Could not parse 'x': invalid digit found in string".to_string()
+            )
+        );
+        assert_eq!(
+            game_id_sum("Game 1: 1 red\nGame x: 1 red", &bag),
+            Err("line 2, column 6: Could not parse 'x': invalid digit found in string".to_string())
+        );
+        assert!(Game::parse("Game 2: 1 red, 4").is_err());
+        let err = Game::parse("Round 2: 1 red").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+
+    #[test]
+    fn minimal_bags() {
+        let bag = Bag::parse(ELF_BAG).unwrap();
+        let games = parse_games(DOCUMENT_TEST).unwrap();
+
+        // Game 3 needs 20 red from its first round and 13 green from its second.
+        let minimal_bag = games[2].minimal_bag();
+        assert_eq!(
+            Some(&Requirement {
+                amount: 20,
+                round: 0
+            }),
+            minimal_bag.get("red")
+        );
+        assert_eq!(
+            Some(&Requirement {
+                amount: 13,
+                round: 1
+            }),
+            minimal_bag.get("green")
+        );
+        assert_eq!(1560, games[2].power(&bag));
+
+        assert_eq!(
+            Ok(vec![Violation {
+                round: 0,
+                color: "red",
+                amount: 20,
+                limit: 12
+            }]),
+            games[2].violations(&bag)
+        );
+
+        let report = game_report(DOCUMENT_TEST, &bag).unwrap();
+        assert!(report.starts_with(
+            "Game 1: power 48, minimal bag 6 blue (round 2), 2 green (round 2), 4 red (round 1)\n"
+        ));
+        assert!(report.contains("  impossible: round 3 has 15 blue but the bag holds 14\n"));
+        assert_eq!(3, report.matches("impossible").count());
     }
 }
@@ -1,18 +1,28 @@
 mod day1;
 mod day2;
 
+use crate::solutions::Solver;
+
 pub fn select_day(args: &[String]) {
     let module = args.get(2).map_or_else(
         || {
             println!("No day name specified, defaulting to day1.");
             "day1"
         },
-        |module| module,
+        String::as_str,
     );
 
     match module {
-        "day1" => day1::main(),
-        "day2" => day2::main(),
+        "day1" => day1::main(args),
+        "day2" => day2::main(args),
         _ => println!("Invalid module name."),
     }
 }
+
+pub fn solver(day: u32) -> Option<Solver> {
+    match day {
+        1 => Some(day1::solve),
+        2 => Some(day2::solve),
+        _ => None,
+    }
+}